@@ -0,0 +1,339 @@
+use std::{
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use chrono::NaiveDate;
+use flate2::{write::GzEncoder, Compression};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+use crate::{
+    config::{construct_log_file_path, get_all_log_dates},
+    error::Error,
+};
+
+/// The name of the file in a backup that lists the dates of the backed up log files.
+const MANIFEST_FILE_NAME: &str = "MANIFEST";
+
+/// Returns true if the log files are archived to a compressed tar file at `target_path`,
+/// rather than moved into the directory at `target_path`.
+fn is_tar_gz(target_path: &Path) -> bool {
+    let file_name = target_path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+    file_name.ends_with(".tar.gz") || file_name.ends_with(".tgz")
+}
+
+/// Returns the paths of the log files of the days before `before`, oldest first.
+pub fn log_files_before(log_dir_path: &Path, before: NaiveDate) -> Result<Vec<PathBuf>, Error> {
+    let dates = get_all_log_dates(log_dir_path).map_err(Error::Io)?;
+    Ok(dates
+        .iter()
+        .filter(|date| **date < before)
+        .map(|date| construct_log_file_path(log_dir_path, date))
+        .collect())
+}
+
+/// Creates a new file at `file_path` and writes it with `write`. The partly written file
+/// is removed if `write` fails, so that a retry isn't refused because it already exists.
+fn create_archive(
+    file_path: &Path,
+    write: impl FnOnce(File) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+    let file = File::create_new(file_path)?;
+    let result = write(file);
+    if result.is_err() {
+        // The error of writing is more useful than the one of removing
+        let _ = std::fs::remove_file(file_path);
+    }
+    result
+}
+
+/// Writes `file_paths` to a new compressed tar file at `archive_path`.
+fn write_tar_gz(archive_path: &Path, file_paths: &[PathBuf]) -> std::io::Result<()> {
+    create_archive(archive_path, |archive_file| {
+        let mut builder = tar::Builder::new(GzEncoder::new(archive_file, Compression::default()));
+        for file_path in file_paths {
+            let file_name = file_path.file_name().expect("A log file has a file name");
+            builder.append_path_with_name(file_path, file_name)?;
+        }
+        builder.into_inner()?.finish()?;
+        Ok(())
+    })
+}
+
+/// Writes `file_paths` to a new zip file at `zip_path`, followed by a `MANIFEST_FILE_NAME`
+/// file with the content of `manifest`. The log files are streamed in as they are.
+fn write_zip(zip_path: &Path, file_paths: &[PathBuf], manifest: &str) -> std::io::Result<()> {
+    create_archive(zip_path, |zip_file| {
+        let mut writer = ZipWriter::new(zip_file);
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        for file_path in file_paths {
+            let file_name = file_path.file_name().expect("A log file has a file name");
+            writer.start_file(file_name.to_string_lossy(), options)?;
+            std::io::copy(&mut File::open(file_path)?, &mut writer)?;
+        }
+        writer.start_file(MANIFEST_FILE_NAME, options)?;
+        writer.write_all(manifest.as_bytes())?;
+        writer.finish()?;
+        Ok(())
+    })
+}
+
+/// Moves `file_paths` into the directory at `dir_path`, creating it if needed.
+/// Nothing is moved if one of the files already exists in the directory.
+fn move_to_dir(dir_path: &Path, file_paths: &[PathBuf]) -> Result<(), Error> {
+    let target_paths: Vec<PathBuf> = file_paths
+        .iter()
+        .map(|file_path| dir_path.join(file_path.file_name().expect("A log file has a file name")))
+        .collect();
+    if let Some(target_path) = target_paths.iter().find(|path| path.exists()) {
+        return Err(Error::ArchiveTargetExists(target_path.to_owned()));
+    }
+
+    std::fs::create_dir_all(dir_path).map_err(Error::Io)?;
+    for (file_path, target_path) in file_paths.iter().zip(&target_paths) {
+        // Renaming fails across file systems, so fall back to copying
+        if std::fs::rename(file_path, target_path).is_err() {
+            std::fs::copy(file_path, target_path).map_err(Error::Io)?;
+            std::fs::remove_file(file_path).map_err(Error::Io)?;
+        }
+    }
+    Ok(())
+}
+
+/// Moves the log files of the days before `before` out of `log_dir_path`, into a
+/// `.tar.gz`/`.tgz` archive or a directory at `target_path`, and prints them.
+/// The archived log files are only removed once the archive is written.
+/// Nothing is changed if `dry_run` is true. Nothing is printed if `quiet` is true,
+/// unless `dry_run` is true too.
+pub fn archive_log_files(
+    log_dir_path: &Path,
+    before: NaiveDate,
+    target_path: &Path,
+    dry_run: bool,
+    quiet: bool,
+) -> Result<(), Error> {
+    let quiet = quiet && !dry_run;
+    let file_paths = log_files_before(log_dir_path, before)?;
+    if file_paths.is_empty() {
+        if !quiet {
+            println!("Nothing to archive.");
+        }
+        return Ok(());
+    }
+    let tar_gz = is_tar_gz(target_path);
+    if tar_gz && target_path.exists() {
+        return Err(Error::ArchiveTargetExists(target_path.to_owned()));
+    }
+
+    let verb = if dry_run { "Would archive" } else { "Archived" };
+    if !dry_run {
+        if tar_gz {
+            write_tar_gz(target_path, &file_paths).map_err(Error::Io)?;
+            for file_path in &file_paths {
+                std::fs::remove_file(file_path).map_err(Error::Io)?;
+            }
+        } else {
+            move_to_dir(target_path, &file_paths)?;
+        }
+    }
+    if quiet {
+        return Ok(());
+    }
+    for file_path in &file_paths {
+        println!("{} '{}'", verb, file_path.display());
+    }
+    let file_count = if file_paths.len() == 1 {
+        "1 log file".to_owned()
+    } else {
+        format!("{} log files", file_paths.len())
+    };
+    println!("{} {} to '{}'", verb, file_count, target_path.display());
+    Ok(())
+}
+
+/// Copies all log files in `log_dir_path` unchanged into a new zip file at `backup_path`,
+/// with a `MANIFEST_FILE_NAME` file listing their dates, one per line.
+/// Unlike `archive_log_files`, the log files are kept. Nothing is printed if `quiet` is true.
+pub fn backup_log_files(log_dir_path: &Path, backup_path: &Path, quiet: bool) -> Result<(), Error> {
+    if !log_dir_path.exists() {
+        return Err(Error::LogDirNotFound(log_dir_path.to_path_buf()));
+    }
+    let is_zip = backup_path
+        .extension()
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("zip"));
+    if !is_zip {
+        return Err(Error::NotAZipFile(backup_path.to_owned()));
+    }
+    if backup_path.exists() {
+        return Err(Error::ArchiveTargetExists(backup_path.to_owned()));
+    }
+
+    let dates = get_all_log_dates(log_dir_path).map_err(Error::Io)?;
+    let file_paths: Vec<PathBuf> = dates
+        .iter()
+        .map(|date| construct_log_file_path(log_dir_path, date))
+        .collect();
+    let manifest: String = dates.iter().map(|date| format!("{}\n", date)).collect();
+    write_zip(backup_path, &file_paths, &manifest).map_err(Error::Io)?;
+    if quiet {
+        return Ok(());
+    }
+
+    let file_count = if file_paths.len() == 1 {
+        "1 log file".to_owned()
+    } else {
+        format!("{} log files", file_paths.len())
+    };
+    println!("Backed up {} to '{}'", file_count, backup_path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use chrono::NaiveDate;
+    use flate2::read::GzDecoder;
+
+    use crate::{config::construct_log_file_path, error::Error};
+
+    use super::{archive_log_files, backup_log_files, log_files_before, MANIFEST_FILE_NAME};
+
+    fn write_log_files(log_dir_path: &Path) {
+        for day in ["2022-12-31", "2023-01-01", "2023-05-02"] {
+            let date = NaiveDate::parse_from_str(day, "%Y-%m-%d").unwrap();
+            let content = format!("[{} 12:00] {}\n", day, day);
+            std::fs::write(construct_log_file_path(log_dir_path, &date), content).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_archive_to_dir() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let archive_dir = tempfile::tempdir().unwrap();
+        let archive_dir_path = archive_dir.path().join("old");
+        write_log_files(log_dir.path());
+        let before = NaiveDate::from_ymd_opt(2023, 5, 1).unwrap();
+
+        archive_log_files(log_dir.path(), before, &archive_dir_path, true, true).unwrap();
+        assert_eq!(log_files_before(log_dir.path(), before).unwrap().len(), 2);
+        assert!(!archive_dir_path.exists());
+
+        archive_log_files(log_dir.path(), before, &archive_dir_path, false, true).unwrap();
+        assert!(log_files_before(log_dir.path(), before).unwrap().is_empty());
+        assert!(log_dir.path().join("2023-05-02.log").exists());
+        assert_eq!(
+            std::fs::read_to_string(archive_dir_path.join("2022-12-31.log")).unwrap(),
+            "[2022-12-31 12:00] 2022-12-31\n"
+        );
+        assert!(archive_dir_path.join("2023-01-01.log").exists());
+
+        // An archived file isn't overwritten, and nothing is moved then
+        write_log_files(log_dir.path());
+        let result = archive_log_files(log_dir.path(), before, &archive_dir_path, false, true);
+        assert!(matches!(result, Err(Error::ArchiveTargetExists(_))));
+        assert_eq!(log_files_before(log_dir.path(), before).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_archive_to_tar_gz() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let archive_dir = tempfile::tempdir().unwrap();
+        let archive_path = archive_dir.path().join("archive.tar.gz");
+        write_log_files(log_dir.path());
+        let before = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+
+        archive_log_files(log_dir.path(), before, &archive_path, false, true).unwrap();
+        assert!(!log_dir.path().join("2022-12-31.log").exists());
+        assert!(log_dir.path().join("2023-01-01.log").exists());
+
+        let archive_file = std::fs::File::open(&archive_path).unwrap();
+        let mut archive = tar::Archive::new(GzDecoder::new(archive_file));
+        let names: Vec<String> = archive
+            .entries()
+            .unwrap()
+            .map(|entry| entry.unwrap().path().unwrap().display().to_string())
+            .collect();
+        assert_eq!(names, ["2022-12-31.log"]);
+
+        // A partly written archive is removed
+        let missing_file_path = log_dir.path().join("2022-01-01.log");
+        let archive_path = archive_dir.path().join("partial.tar.gz");
+        assert!(super::write_tar_gz(&archive_path, &[missing_file_path]).is_err());
+        assert!(!archive_path.exists());
+        let archive_path = archive_dir.path().join("archive.tar.gz");
+
+        // An existing archive isn't overwritten
+        let before = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let result = archive_log_files(log_dir.path(), before, &archive_path, false, true);
+        assert!(matches!(result, Err(Error::ArchiveTargetExists(_))));
+        assert!(log_dir.path().join("2023-01-01.log").exists());
+    }
+
+    #[test]
+    fn test_backup_log_files() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let backup_dir = tempfile::tempdir().unwrap();
+        let backup_path = backup_dir.path().join("backup.zip");
+        write_log_files(log_dir.path());
+        // A log file that isn't valid UTF-8 is copied as it is
+        std::fs::write(
+            log_dir.path().join("2023-05-03.log"),
+            b"[2023-05-03 12:00] \xff\n",
+        )
+        .unwrap();
+
+        let result = backup_log_files(
+            log_dir.path(),
+            &backup_dir.path().join("backup.tar.gz"),
+            true,
+        );
+        assert!(matches!(result, Err(Error::NotAZipFile(_))));
+
+        backup_log_files(log_dir.path(), &backup_path, true).unwrap();
+        assert!(log_dir.path().join("2022-12-31.log").exists());
+
+        let backup_file = std::fs::File::open(&backup_path).unwrap();
+        let mut backup = zip::ZipArchive::new(backup_file).unwrap();
+        let files: Vec<(String, Vec<u8>)> = (0..backup.len())
+            .map(|index| {
+                let mut file = backup.by_index(index).unwrap();
+                let name = file.name().to_owned();
+                let mut content = Vec::new();
+                std::io::Read::read_to_end(&mut file, &mut content).unwrap();
+                (name, content)
+            })
+            .collect();
+        let names: Vec<&str> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "2022-12-31.log",
+                "2023-01-01.log",
+                "2023-05-02.log",
+                "2023-05-03.log",
+                MANIFEST_FILE_NAME
+            ]
+        );
+        assert_eq!(files[0].1, b"[2022-12-31 12:00] 2022-12-31\n");
+        assert_eq!(files[3].1, b"[2023-05-03 12:00] \xff\n");
+        assert_eq!(
+            files[4].1,
+            b"2022-12-31\n2023-01-01\n2023-05-02\n2023-05-03\n"
+        );
+
+        // A partly written backup is removed
+        let missing_file_path = log_dir.path().join("2022-01-01.log");
+        let partial_path = backup_dir.path().join("partial.zip");
+        assert!(super::write_zip(&partial_path, &[missing_file_path], "").is_err());
+        assert!(!partial_path.exists());
+
+        // An existing backup isn't overwritten
+        let result = backup_log_files(log_dir.path(), &backup_path, true);
+        assert!(matches!(result, Err(Error::ArchiveTargetExists(_))));
+    }
+}
@@ -0,0 +1,185 @@
+use std::{collections::HashSet, io::Write, path::Path};
+
+use chrono::{Datelike, Days, Locale, Months, NaiveDate};
+use crossterm::style::Stylize;
+
+use crate::{
+    config::{read_all_log_item_lists, FirstWeekday},
+    error::Error,
+    utils::{output::Output, time::weekday_name},
+};
+
+/// The format of the month argument of the calendar command.
+const MONTH_FORMAT: &str = "%Y-%m";
+
+/// Parses a month in `MONTH_FORMAT` and returns its first day.
+pub fn parse_month(month_str: &str) -> Result<NaiveDate, Error> {
+    NaiveDate::parse_from_str(&format!("{}-01", month_str), "%Y-%m-%d").map_err(|error| {
+        Error::DateParse {
+            input: month_str.to_owned(),
+            source: error,
+            formats: vec![MONTH_FORMAT.to_owned()],
+        }
+    })
+}
+
+/// Returns the header of the weekday columns, starting with `first_weekday`.
+/// Each weekday is the first two characters of its name in `locale`.
+pub fn weekday_header(first_weekday: FirstWeekday, locale: Locale) -> String {
+    // Any date of the weekday gives its name
+    let mut date =
+        NaiveDate::from_isoywd_opt(2024, 1, first_weekday.weekday()).expect("the week exists");
+    let mut names: Vec<String> = Vec::new();
+    for _ in 0..7 {
+        let name: String = weekday_name(date, locale).chars().take(2).collect();
+        names.push(format!("{:<2}", name));
+        date = date + Days::new(1);
+    }
+    names.join(" ")
+}
+
+/// Returns the lines of the calendar of the month of `date`. Each day takes three columns,
+/// and the days in `marked_dates` are followed by a '*', or colored if `colored` is true.
+pub fn calendar_lines(
+    date: NaiveDate,
+    first_weekday: FirstWeekday,
+    locale: Locale,
+    marked_dates: &HashSet<NaiveDate>,
+    colored: bool,
+) -> Vec<String> {
+    let first_day = date.with_day(1).expect("every month has a first day");
+    let next_month_first_day = first_day + Months::new(1);
+
+    let mut lines = vec![
+        format!(
+            "{:^20}",
+            first_day.format_localized("%B %Y", locale).to_string()
+        )
+        .trim_end()
+        .to_owned(),
+        weekday_header(first_weekday, locale),
+    ];
+    let offset = first_day.weekday().days_since(first_weekday.weekday()) as usize;
+    let mut line = "   ".repeat(offset);
+    let mut day = first_day;
+    while day < next_month_first_day {
+        let day_str = format!("{:>2}", day.day());
+        let is_marked = marked_dates.contains(&day);
+        if is_marked && colored {
+            line.push_str(&format!("{} ", day_str.green().bold()));
+        } else {
+            line.push_str(&day_str);
+            line.push(if is_marked { '*' } else { ' ' });
+        }
+        if day.weekday().succ() == first_weekday.weekday() {
+            lines.push(line.trim_end().to_owned());
+            line.clear();
+        }
+        day = day + Days::new(1);
+    }
+    if !line.is_empty() {
+        lines.push(line.trim_end().to_owned());
+    }
+    lines
+}
+
+/// Prints the calendar of the month of `date`, marking the days with log messages.
+pub fn print_calendar(
+    log_dir_path: &Path,
+    date: NaiveDate,
+    first_weekday: FirstWeekday,
+    locale: Locale,
+) -> Result<(), Error> {
+    if !log_dir_path.exists() {
+        return Err(Error::LogDirNotFound(log_dir_path.to_path_buf()));
+    }
+
+    // A day with an empty log file isn't marked
+    let marked_dates: HashSet<NaiveDate> = read_all_log_item_lists(log_dir_path, false)
+        .map_err(Error::Io)?
+        .into_iter()
+        .filter(|(_, log_item_list)| !log_item_list.is_empty())
+        .map(|(date, _)| date)
+        .collect();
+
+    let mut output = Output::new(None).map_err(Error::Io)?;
+    let colored = output.is_terminal();
+    for line in calendar_lines(date, first_weekday, locale, &marked_dates, colored) {
+        writeln!(output, "{}", line).map_err(Error::Io)?;
+    }
+    output.flush().map_err(Error::Io)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+
+    use chrono::{Locale, NaiveDate};
+
+    use crate::config::FirstWeekday;
+
+    use super::{calendar_lines, parse_month, weekday_header};
+
+    #[test]
+    fn test_weekday_header() {
+        assert_eq!(
+            weekday_header(FirstWeekday::Monday, Locale::en_US),
+            "Mo Tu We Th Fr Sa Su"
+        );
+        assert_eq!(
+            weekday_header(FirstWeekday::Sunday, Locale::en_US),
+            "Su Mo Tu We Th Fr Sa"
+        );
+        assert_eq!(
+            weekday_header(FirstWeekday::Monday, Locale::de_DE),
+            "Mo Di Mi Do Fr Sa So"
+        );
+    }
+
+    #[test]
+    fn test_calendar_lines() {
+        let date = parse_month("2025-02").unwrap();
+        let marked_dates = HashSet::from([
+            NaiveDate::from_ymd_opt(2025, 2, 3).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+        ]);
+
+        assert_eq!(
+            calendar_lines(
+                date,
+                FirstWeekday::Monday,
+                Locale::en_US,
+                &marked_dates,
+                false
+            ),
+            [
+                "   February 2025",
+                "Mo Tu We Th Fr Sa Su",
+                "                1  2",
+                " 3* 4  5  6  7  8  9",
+                "10 11 12 13 14 15 16",
+                "17 18 19 20 21 22 23",
+                "24 25 26 27 28",
+            ]
+        );
+        assert_eq!(
+            calendar_lines(
+                date,
+                FirstWeekday::Sunday,
+                Locale::en_US,
+                &marked_dates,
+                false
+            ),
+            [
+                "   February 2025",
+                "Su Mo Tu We Th Fr Sa",
+                "                   1",
+                " 2  3* 4  5  6  7  8",
+                " 9 10 11 12 13 14 15",
+                "16 17 18 19 20 21 22",
+                "23 24 25 26 27 28",
+            ]
+        );
+        assert!(parse_month("2025-13").is_err());
+    }
+}
@@ -1,6 +1,9 @@
-use clap::{command, Subcommand};
+use std::path::PathBuf;
+
+use clap::Subcommand;
 
 use crate::constants::{PKG_NAME, PKG_VERSION};
+use crate::log_item::LOG_ITEM_TEMPLATE_PLACEHOLDERS;
 
 // "Path to the output file or directory. Errors if the path doesn't exist. Uses the file if it's a file; creates/uses a log file in the directory if it's a directory."
 
@@ -9,8 +12,55 @@ pub enum Commands {
     /// Writes a message to the log file
     Write {
         /// The message to write
-        #[arg(short, long, help = "The content of the message you want to write.")]
-        message: Option<String>,
+        #[arg(
+            short,
+            long,
+            action = clap::ArgAction::Append,
+            help = "The content of the message you want to write. If given multiple times, the values are joined as separate paragraphs."
+        )]
+        message: Vec<String>,
+
+        /// Read one message per line from stdin
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            conflicts_with = "message",
+            help = "Read the messages from stdin, writing each non-empty line as a separate message."
+        )]
+        lines: bool,
+
+        /// Reject long messages
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Abort instead of warning if the message is longer than `log.max_entry_chars`."
+        )]
+        strict: bool,
+
+        /// The day to write to
+        #[arg(
+            long,
+            value_name = "DATE",
+            help = "Write to the log file of an earlier day, such as `yesterday`, at the current time of day."
+        )]
+        day: Option<String>,
+
+        /// File to seed the editor with
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = ["message", "lines"],
+            help = "Pre-fill the editor with the content of the file instead of `log.template`, e.g. a daily checklist."
+        )]
+        template_file: Option<PathBuf>,
+
+        /// Print the time since the previous entry
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Print the time since the previous log message of the day after writing. It's also printed in verbose mode."
+        )]
+        since_last: bool,
 
         /// Print more output
         #[arg(short, long, action = clap::ArgAction::SetTrue, help = "Enable verbose mode.")]
@@ -18,6 +68,7 @@ pub enum Commands {
     },
 
     /// Views the stored log messages
+    #[command(visible_alias = "today")]
     View {
         /// Date to view
         #[arg(
@@ -29,6 +80,84 @@ pub enum Commands {
         #[arg(short, long, action = clap::ArgAction::SetTrue, help = "View all logs in one page.")]
         all: bool,
 
+        /// Don't use the alternate screen
+        #[arg(
+            long = "no-altscreen",
+            action = clap::ArgAction::SetTrue,
+            help = "Run the pager without the alternate screen, leaving the last page in the scrollback."
+        )]
+        no_altscreen: bool,
+
+        /// Don't wrap long lines
+        #[arg(
+            long = "no-wrap",
+            action = clap::ArgAction::SetTrue,
+            help = "Don't wrap the lines longer than the terminal width. They can be scrolled horizontally."
+        )]
+        no_wrap: bool,
+
+        /// Sort the log messages of each day
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            requires = "all",
+            help = "Sort the log messages of each day by time. The ones with the same time keep their order in the log file. Only with `--all`."
+        )]
+        sorted: bool,
+
+        /// Start at the end
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            conflicts_with = "all",
+            help = "Open the pager at the end of the day, where the newest log messages are."
+        )]
+        bottom: bool,
+
+        /// Read the log content from stdin
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            conflicts_with_all = ["date", "all"],
+            help = "Page the log content read from stdin instead of a log file. The day navigation and the editing are disabled."
+        )]
+        stdin: bool,
+
+        /// Pick the date from a list
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            conflicts_with_all = ["date", "all", "stdin"],
+            help = "Pick the date to view from a list of the dates with logs, most recent first."
+        )]
+        pick: bool,
+
+        /// Search pattern to start with
+        #[arg(
+            long,
+            requires = "all",
+            help = "Open the pager with the search pattern applied, at the first match. Only with `--all`."
+        )]
+        search: Option<String>,
+
+        /// Only load the most recent days
+        #[arg(
+            long,
+            value_name = "N",
+            requires = "all",
+            help = "Only load the most recent N days with logs, older entries are not shown. Only with `--all`."
+        )]
+        limit: Option<usize>,
+
+        /// Leave out today
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            requires = "all",
+            help = "Leave out today's log messages, e.g. to review only the completed days. Only with `--all`."
+        )]
+        exclude_today: bool,
+
         /// Print more output
         #[arg(short, long, action = clap::ArgAction::SetTrue, help = "Enable verbose mode.")]
         verbose: bool,
@@ -37,13 +166,481 @@ pub enum Commands {
     /// Configure options
     Config {
         /// Configuration key (e.g., user.email)
-        key: String,
+        #[arg(
+            required_unless_present_any = ["repair", "dump"],
+            help = "The configuration key, such as `log.dir`. The read-only key `config.path` is the path of the config file."
+        )]
+        key: Option<String>,
 
         /// Configuration value (optional, e.g., xxx.com)
         value: Option<String>,
+
+        /// Repair the config file
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            conflicts_with_all = ["key", "value"],
+            help = "Back up the config file and regenerate a default one, if it can't be loaded."
+        )]
+        repair: bool,
+
+        /// Reset the key to its default value
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            conflicts_with = "value",
+            help = "Remove the key from the config file, so that it has its default value."
+        )]
+        unset: bool,
+
+        /// Print the resolved configuration
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            conflicts_with_all = ["key", "value", "repair", "unset"],
+            help = "Print the configuration in use as TOML, including the default values and the log directory set by the environment."
+        )]
+        dump: bool,
+    },
+
+    /// Removes the last message written today
+    Undo,
+
+    /// Prints the log directory
+    Dir {
+        /// Open the log directory
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Open the log directory in the file manager instead of printing it."
+        )]
+        open: bool,
+    },
+
+    /// Prints the path of a day's log file, for scripts
+    Path {
+        /// Date of the log file
+        #[arg(
+            help = "The date of the log file. If no date is provided, today's date will be used. The path is printed whether the file exists or not."
+        )]
+        date: Option<String>,
+
+        /// Print the paths of all log files
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            conflicts_with = "date",
+            help = "Print the paths of all existing log files, oldest first."
+        )]
+        all: bool,
+
+        /// Fail if the log file doesn't exist
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Print nothing and exit with 1 if the log file doesn't exist."
+        )]
+        exists_only: bool,
+    },
+
+    /// Shows statistics of the log messages
+    Stats {
+        /// Group the log messages
+        #[arg(long, value_enum, help = "Count the log messages in each group.")]
+        by: Option<StatsGroupBy>,
+
+        /// Print JSON
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Print the result as JSON."
+        )]
+        json: bool,
+
+        /// Leave out today
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Leave out today's log messages, e.g. to review only the completed days."
+        )]
+        exclude_today: bool,
+    },
+
+    /// Shows the current and the longest streaks of consecutive days with log messages
+    Streak {
+        /// Print JSON
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Print the result as JSON."
+        )]
+        json: bool,
+
+        /// Leave out today
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Leave out today's log messages, e.g. to review only the completed days."
+        )]
+        exclude_today: bool,
+    },
+
+    /// Shows the most frequent words in the log messages
+    Words {
+        /// Number of words to show
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = 10,
+            help = "The number of words to show, most frequent first."
+        )]
+        top: usize,
+
+        /// Count the stopwords
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Count the common words such as `the` and `and` too, which are left out by default."
+        )]
+        no_stopwords: bool,
+    },
+
+    /// Shows a calendar of a month with the days with log messages marked
+    Calendar {
+        /// Month to show
+        #[arg(
+            help = "The month to show in '%Y-%m' format. If no month is provided, the current month will be used."
+        )]
+        month: Option<String>,
+    },
+
+    /// Shows the log messages written on today's month and day in earlier years
+    #[command(name = "onthisday")]
+    OnThisDay {
+        /// Date to look back from
+        #[arg(
+            help = "The date whose month and day are looked up in earlier years. If no date is provided, today's date will be used."
+        )]
+        date: Option<String>,
+    },
+
+    /// Shows the differences between the log messages of two days
+    Diff {
+        /// Date to compare from
+        #[arg(help = "The date of the old side of the diff. A missing log file is empty.")]
+        old_date: String,
+
+        /// Date to compare to
+        #[arg(help = "The date of the new side of the diff. A missing log file is empty.")]
+        new_date: String,
+    },
+
+    /// Sorts the log messages of a day by time and rewrites its log file
+    Tidy {
+        /// Date to tidy
+        #[arg(
+            help = "The date of the logs to tidy in '%Y-%m-%d' format. If no date is provided, today's date will be used."
+        )]
+        date: Option<String>,
+
+        /// Tidy every day
+        #[arg(
+            short,
+            long,
+            action = clap::ArgAction::SetTrue,
+            conflicts_with = "date",
+            help = "Tidy the log files of all days."
+        )]
+        all: bool,
+
+        /// Only report what would be changed
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Print the log files that would be changed without writing them."
+        )]
+        dry_run: bool,
+    },
+
+    /// Removes the log messages of a day that repeat the one before them
+    Dedupe {
+        /// Date to dedupe
+        #[arg(
+            help = "The date of the logs to dedupe in '%Y-%m-%d' format. If no date is provided, today's date will be used."
+        )]
+        date: Option<String>,
+
+        /// Dedupe every day
+        #[arg(
+            short,
+            long,
+            action = clap::ArgAction::SetTrue,
+            conflicts_with = "date",
+            help = "Dedupe the log files of all days."
+        )]
+        all: bool,
+
+        /// Only report what would be removed
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Print the duplicates that would be removed without writing the log files."
+        )]
+        dry_run: bool,
+    },
+
+    /// Copies all log files into a zip file
+    Backup {
+        /// Backup file
+        #[arg(
+            value_name = "PATH",
+            help = "The `.zip` file to create, with the log files unchanged and a MANIFEST file listing their dates. An existing file isn't overwritten."
+        )]
+        path: PathBuf,
+    },
+
+    /// Moves the old log files out of the log directory
+    Archive {
+        /// Date before which the log files are archived
+        #[arg(
+            long,
+            value_name = "DATE",
+            help = "Archive the log files of the days before this date."
+        )]
+        before: String,
+
+        /// Archive file or directory
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "The archive to create if it ends with `.tar.gz` or `.tgz`, otherwise the directory to move the log files into. Existing files aren't overwritten."
+        )]
+        to: PathBuf,
+
+        /// Only report what would be archived
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Print the log files that would be archived without moving them."
+        )]
+        dry_run: bool,
+    },
+
+    /// Searches the log messages
+    Grep {
+        /// Regular expression to search for
+        #[arg(help = "The regular expression to search for in the log messages.")]
+        pattern: String,
+
+        /// Ignore case distinctions
+        #[arg(short, long, action = clap::ArgAction::SetTrue, help = "Search case-insensitively.")]
+        ignore_case: bool,
+
+        /// Date to search
+        #[arg(
+            short,
+            long,
+            help = "Only search the logs of the date in '%Y-%m-%d' format."
+        )]
+        date: Option<String>,
+
+        /// Minimum number of words
+        #[arg(
+            long,
+            value_name = "N",
+            help = "Only match the log messages with at least N words."
+        )]
+        min_words: Option<usize>,
+
+        /// Maximum number of words
+        #[arg(
+            long,
+            value_name = "N",
+            help = "Only match the log messages with at most N words."
+        )]
+        max_words: Option<usize>,
+
+        /// Only print the number of matches
+        #[arg(
+            short = 'c',
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Print the number of matching log messages instead of the messages."
+        )]
+        count_only: bool,
+
+        /// Print the number of matches of each day
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Print the number of matching log messages of each day instead of the messages."
+        )]
+        count_by_day: bool,
+
+        /// Output format of each log message
+        #[arg(
+            long,
+            help = format!("Print each log message in the format, where the placeholders {} are expanded. The default is '[{{datetime}}] {{content}}'.", LOG_ITEM_TEMPLATE_PLACEHOLDERS)
+        )]
+        template: Option<String>,
+
+        /// Print the number of entries and days
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            conflicts_with_all = ["count_only", "count_by_day"],
+            help = "Print the number of printed log messages and of their days to stderr at the end, unless `--quiet` is given."
+        )]
+        summary: bool,
+
+        /// File to write to
+        #[arg(short, long, help = "Write the result to the file instead of stdout.")]
+        output: Option<PathBuf>,
+    },
+
+    /// Shows the most recent log messages
+    Recent {
+        /// Number of log messages to show
+        #[arg(
+            default_value_t = 10,
+            help = "The number of log messages to show, newest first."
+        )]
+        count: usize,
+
+        /// Number of log messages to show, like COUNT
+        #[arg(
+            short = 'n',
+            long,
+            value_name = "N",
+            conflicts_with = "count",
+            help = "The number of log messages to show, like COUNT."
+        )]
+        entries: Option<usize>,
+
+        /// Output format of each log message
+        #[arg(
+            long,
+            help = format!("Print each log message in the format, where the placeholders {} are expanded. The default is '[{{datetime}}] {{content}}'.", LOG_ITEM_TEMPLATE_PLACEHOLDERS)
+        )]
+        template: Option<String>,
+
+        /// Print the number of entries and days
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Print the number of printed log messages and of their days to stderr at the end, unless `--quiet` is given."
+        )]
+        summary: bool,
+    },
+
+    /// Prints the content of the latest log message, for scripts
+    Last {
+        /// Print the date
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Print the date and time before the content, as in the log file."
+        )]
+        with_date: bool,
+    },
+
+    /// Shows the log message with the ID
+    Show {
+        /// ID of the log message
+        #[arg(help = "The ID of the log message shown by the pagers, or a prefix of it.")]
+        id: String,
+    },
+
+    /// Exports all log messages
+    Export {
+        /// Format of the exported logs
+        #[arg(
+            short,
+            long,
+            value_enum,
+            default_value_t = ExportFormat::Log,
+            help = "The format of the exported logs."
+        )]
+        format: ExportFormat,
+
+        /// First date to export
+        #[arg(
+            long,
+            help = "Only export the logs since the date, inclusive. The date is parsed with `log.date_input_formats`."
+        )]
+        since: Option<String>,
+
+        /// Last date to export
+        #[arg(
+            long,
+            help = "Only export the logs until the date, inclusive. The date is parsed with `log.date_input_formats`."
+        )]
+        until: Option<String>,
+
+        /// Merge all days into one stream
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Write the log messages of all days sorted by time, without date headers."
+        )]
+        flat: bool,
+
+        /// Minimum number of words
+        #[arg(
+            long,
+            value_name = "N",
+            help = "Only export the log messages with at least N words."
+        )]
+        min_words: Option<usize>,
+
+        /// Maximum number of words
+        #[arg(
+            long,
+            value_name = "N",
+            help = "Only export the log messages with at most N words."
+        )]
+        max_words: Option<usize>,
+
+        /// Leave out today
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Leave out today's log messages, e.g. to review only the completed days."
+        )]
+        exclude_today: bool,
+
+        /// File to write to
+        #[arg(short, long, help = "Write the result to the file instead of stdout.")]
+        output: Option<PathBuf>,
+    },
+
+    /// Imports log messages from a file
+    Import {
+        /// File to import
+        #[arg(help = "The file to import the log messages from.")]
+        file: PathBuf,
+
+        /// Format of the imported file
+        #[arg(
+            short,
+            long,
+            value_enum,
+            default_value_t = ImportFormat::Log,
+            help = "The format of the imported file."
+        )]
+        format: ImportFormat,
+
+        /// Only report what would be imported
+        #[arg(
+            long,
+            action = clap::ArgAction::SetTrue,
+            help = "Print what would be imported without writing the log files."
+        )]
+        dry_run: bool,
     },
 
     /// Edit logs
+    #[command(visible_alias = "open")]
     Edit {
         /// Date to edit
         #[arg(
@@ -57,11 +654,59 @@ pub enum Commands {
     },
 }
 
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum StatsGroupBy {
+    /// Day of the week
+    Weekday,
+    /// ISO week
+    Week,
+    /// Month
+    Month,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ExportFormat {
+    /// The format of the log files
+    Log,
+    /// Plain text with a separator line before each day
+    Text,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum ImportFormat {
+    /// The format of the log files
+    Log,
+    /// An array of objects with the fields `date_time` ('%Y-%m-%d %H:%M') and `content`
+    Json,
+}
+
 #[derive(clap::Parser)]
 #[command(name = PKG_NAME)]
 #[command(version = PKG_VERSION)]
 #[command(about = "A logger tool for keeping a diary.", long_about = None)]
+#[command(after_help = "Environment variables:
+  MYLOG_CONFIG_DIR  The directory of the default config file
+  MYLOG_DIR         The log directory, which takes precedence over `log.dir` of the config")]
 pub struct Cli {
+    /// Config file to use
+    #[arg(
+        long,
+        global = true,
+        value_name = "FILE",
+        help = "Use the config file instead of the default one. Unlike the default one, it isn't created if missing."
+    )]
+    pub config: Option<PathBuf>,
+
+    /// Don't print informational messages
+    #[arg(
+        short,
+        long,
+        global = true,
+        action = clap::ArgAction::SetTrue,
+        help = "Don't print informational messages, such as the one after writing a log message. Errors are still printed."
+    )]
+    pub quiet: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -1,8 +1,11 @@
 use crate::{
-    constants::{CONFIG_DIR_ENV_VAR, PKG_NAME},
-    error, Error,
+    constants::{CONFIG_DIR_ENV_VAR, LOG_DIR_ENV_VAR, PKG_NAME},
+    error,
+    log_item::{LogItem, LogItemList},
+    utils::time::get_today_date,
+    Error,
 };
-use chrono::NaiveDate;
+use chrono::{Locale, NaiveDate};
 use serde::{Deserialize, Serialize};
 use std::{
     fs::{create_dir_all, File},
@@ -10,25 +13,334 @@ use std::{
     path::{Path, PathBuf},
 };
 
-#[derive(Deserialize, Serialize, PartialEq, Debug, Default)]
+#[derive(Deserialize, Serialize, PartialEq, Debug)]
 pub struct LogConfig {
+    /// The log directory, unless the environment variable `MYLOG_DIR` overrides it.
+    #[serde(default)]
     pub dir: String,
+    /// Whether lines starting with '#' are removed from a message written in the editor.
+    /// When disabled, only the instructional lines inserted by mylog are removed.
+    #[serde(default = "default_strip_comments")]
+    pub strip_comments: bool,
+    /// The `strftime` formats tried in order when parsing a date argument.
+    #[serde(default = "default_date_input_formats")]
+    pub date_input_formats: Vec<String>,
+    /// Whether a date typed without a year that would be after today is in the previous
+    /// year instead, e.g. `12-25` typed in June is the last Christmas.
+    #[serde(default = "default_yearless_dates_in_past")]
+    pub yearless_dates_in_past: bool,
+    /// The format of the date line in the pager. The placeholders `{date}`, `{weekday}`,
+    /// `{count}` (the number of log items) and `{percent}` (how far the page is scrolled)
+    /// are expanded.
+    #[serde(default = "default_prompt_format")]
+    pub prompt_format: String,
+    /// The maximum number of characters of a written log message. Longer messages
+    /// are written with a warning, or rejected by `write --strict`. Unset means no limit.
+    #[serde(default)]
+    pub max_entry_chars: Option<usize>,
+    /// The text the editor is pre-filled with when writing a message, above the instructions.
+    /// A message left as the template is treated as empty.
+    #[serde(default)]
+    pub template: String,
+    /// Whether the instructional comment lines are inserted when writing a message in the editor.
+    #[serde(default = "default_show_instructions")]
+    pub show_instructions: bool,
+    /// The text added before each written log message, e.g. `- `. It isn't added again
+    /// to a message that already starts with it.
+    #[serde(default)]
+    pub entry_prefix: String,
+    /// The text added after each written log message. It isn't added again to a message
+    /// that already ends with it.
+    #[serde(default)]
+    pub entry_suffix: String,
+    /// The editor command, which may have arguments, e.g. `nvim -c 'set ft=markdown'`.
+    /// The file path is appended to it. Empty means the editor of `$VISUAL` or `$EDITOR`.
+    #[serde(default)]
+    pub editor: String,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            dir: String::new(),
+            strip_comments: default_strip_comments(),
+            date_input_formats: default_date_input_formats(),
+            yearless_dates_in_past: default_yearless_dates_in_past(),
+            prompt_format: default_prompt_format(),
+            max_entry_chars: None,
+            template: String::new(),
+            show_instructions: default_show_instructions(),
+            entry_prefix: String::new(),
+            entry_suffix: String::new(),
+            editor: String::new(),
+        }
+    }
+}
+
+fn default_strip_comments() -> bool {
+    true
+}
+
+fn default_show_instructions() -> bool {
+    true
+}
+
+fn default_yearless_dates_in_past() -> bool {
+    true
+}
+
+fn default_date_input_formats() -> Vec<String> {
+    vec!["%Y-%m-%d".to_owned(), "%m-%d".to_owned()]
+}
+
+fn default_prompt_format() -> String {
+    "{date} {weekday}".to_owned()
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone)]
+pub struct DisplayConfig {
+    /// Whether URLs, `#tags` and `@mentions` in log messages are styled in the pagers.
+    #[serde(default = "default_highlight_syntax")]
+    pub highlight_syntax: bool,
+    /// Whether the pagers run in the alternate screen. If not, the last page
+    /// stays in the terminal's scrollback after quitting.
+    #[serde(default = "default_alternate_screen")]
+    pub alternate_screen: bool,
+    /// Whether the ID of each log item is shown next to its date in the pagers.
+    #[serde(default)]
+    pub show_entry_ids: bool,
+    /// Whether the lines longer than the terminal width are wrapped in the pagers.
+    #[serde(default = "default_wrap")]
+    pub wrap: bool,
+    /// The day in the first column of the calendar, `monday` (default) or `sunday`.
+    #[serde(default)]
+    pub first_weekday: FirstWeekday,
+    /// Whether the key hints are shown at the bottom of the single-date pager
+    /// when there is no message.
+    #[serde(default = "default_show_hints")]
+    pub show_hints: bool,
+    /// The number of lines of the current page kept on the screen when scrolling
+    /// by a page or half a page in the pagers.
+    #[serde(default = "default_scroll_overlap")]
+    pub scroll_overlap: usize,
+    /// Whether the log items with a date but no content are hidden in the pagers
+    /// and left out of `stats` and `streak`.
+    #[serde(default)]
+    pub hide_empty_entries: bool,
+    /// The locale of the weekday and month names in the pagers and the calendar,
+    /// e.g. `de_DE`. The default is `en_US`.
+    #[serde(with = "locale_format", default = "default_locale")]
+    pub locale: Locale,
+    /// Whether the top line of the page has a background in the pagers.
+    #[serde(default)]
+    pub highlight_current_line: bool,
+    /// Whether the pagers show the timestamp only on the first of the consecutive log items
+    /// with the same one, indenting the others.
+    #[serde(default)]
+    pub group_same_minute: bool,
+    /// Whether the pager of all days sorts the log items of each day by date time.
+    /// The ones with the same date time keep their order in the log file.
+    #[serde(default)]
+    pub sort_entries: bool,
+    /// Whether the pagers show the current time at the bottom right, redrawn every second.
+    /// It is hidden while a message is shown there.
+    #[serde(default)]
+    pub clock: bool,
+    /// Whether the pagers show the time since each log item, e.g. `2h ago`, instead of
+    /// its date time. Toggled with `t` in the pagers.
+    #[serde(default)]
+    pub relative_time: bool,
+}
+
+mod locale_format {
+    use std::str::FromStr;
+
+    use chrono::Locale;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(locale: &Locale, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&locale.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Locale, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Locale::from_str(&s)
+            .map_err(|_| serde::de::Error::custom(format!("unknown locale `{}`", s)))
+    }
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FirstWeekday {
+    #[default]
+    Monday,
+    Sunday,
+}
+
+impl FirstWeekday {
+    pub fn weekday(self) -> chrono::Weekday {
+        match self {
+            Self::Monday => chrono::Weekday::Mon,
+            Self::Sunday => chrono::Weekday::Sun,
+        }
+    }
+}
+
+impl std::fmt::Display for FirstWeekday {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Monday => write!(f, "monday"),
+            Self::Sunday => write!(f, "sunday"),
+        }
+    }
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            highlight_syntax: default_highlight_syntax(),
+            alternate_screen: default_alternate_screen(),
+            show_entry_ids: false,
+            wrap: default_wrap(),
+            first_weekday: FirstWeekday::default(),
+            show_hints: default_show_hints(),
+            scroll_overlap: default_scroll_overlap(),
+            hide_empty_entries: false,
+            locale: default_locale(),
+            highlight_current_line: false,
+            group_same_minute: false,
+            sort_entries: false,
+            clock: false,
+            relative_time: false,
+        }
+    }
+}
+
+fn default_highlight_syntax() -> bool {
+    true
+}
+
+fn default_alternate_screen() -> bool {
+    true
+}
+
+fn default_wrap() -> bool {
+    true
+}
+
+fn default_show_hints() -> bool {
+    true
+}
+
+fn default_scroll_overlap() -> usize {
+    2
+}
+
+fn default_locale() -> Locale {
+    Locale::en_US
+}
+
+/// The color names of the output, such as `green` or `dark_grey`, resolved by
+/// `Theme::from_config`. The defaults are the colors used before themes existed.
+#[derive(Deserialize, Serialize, PartialEq, Debug, Clone)]
+pub struct ThemeConfig {
+    /// The color of the date and time of the log items.
+    #[serde(default = "default_timestamp_color")]
+    pub timestamp: String,
+    /// The color of the date line and the day separators in the pagers.
+    #[serde(default = "default_date_line_color")]
+    pub date_line: String,
+    /// The background color of the search results.
+    #[serde(default = "default_search_match_color")]
+    pub search_match: String,
+    /// The background color of the error messages in the pagers.
+    #[serde(default = "default_error_color")]
+    pub error: String,
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            timestamp: default_timestamp_color(),
+            date_line: default_date_line_color(),
+            search_match: default_search_match_color(),
+            error: default_error_color(),
+        }
+    }
+}
+
+fn default_timestamp_color() -> String {
+    "green".to_owned()
+}
+
+fn default_date_line_color() -> String {
+    "dark_grey".to_owned()
+}
+
+fn default_search_match_color() -> String {
+    "white".to_owned()
+}
+
+fn default_error_color() -> String {
+    "red".to_owned()
 }
 
 #[derive(Deserialize, Serialize, PartialEq, Debug, Default)]
 pub struct Config {
+    #[serde(default)]
     pub log: LogConfig,
+    #[serde(default)]
+    pub display: DisplayConfig,
+    #[serde(default)]
+    pub theme: ThemeConfig,
 }
 
 impl Config {
-    pub fn get_by_key(&self, key: &str) -> Option<&str> {
+    pub fn get_by_key(&self, key: &str) -> Option<String> {
         match key {
-            "log.dir" => Some(self.log.dir.as_ref()),
+            "log.dir" => Some(self.log.dir.clone()),
+            "log.strip_comments" => Some(self.log.strip_comments.to_string()),
+            "log.date_input_formats" => Some(format!("{:?}", self.log.date_input_formats)),
+            "log.yearless_dates_in_past" => Some(self.log.yearless_dates_in_past.to_string()),
+            "log.prompt_format" => Some(self.log.prompt_format.clone()),
+            "log.max_entry_chars" => Some(
+                self.log
+                    .max_entry_chars
+                    .map(|max_entry_chars| max_entry_chars.to_string())
+                    .unwrap_or_default(),
+            ),
+            "log.template" => Some(self.log.template.clone()),
+            "log.show_instructions" => Some(self.log.show_instructions.to_string()),
+            "log.entry_prefix" => Some(self.log.entry_prefix.clone()),
+            "log.entry_suffix" => Some(self.log.entry_suffix.clone()),
+            "log.editor" => Some(self.log.editor.clone()),
+            "display.highlight_syntax" => Some(self.display.highlight_syntax.to_string()),
+            "display.alternate_screen" => Some(self.display.alternate_screen.to_string()),
+            "display.show_entry_ids" => Some(self.display.show_entry_ids.to_string()),
+            "display.wrap" => Some(self.display.wrap.to_string()),
+            "display.show_hints" => Some(self.display.show_hints.to_string()),
+            "display.scroll_overlap" => Some(self.display.scroll_overlap.to_string()),
+            "display.highlight_current_line" => {
+                Some(self.display.highlight_current_line.to_string())
+            }
+            "display.group_same_minute" => Some(self.display.group_same_minute.to_string()),
+            "display.sort_entries" => Some(self.display.sort_entries.to_string()),
+            "display.clock" => Some(self.display.clock.to_string()),
+            "display.relative_time" => Some(self.display.relative_time.to_string()),
+            "display.locale" => Some(self.display.locale.to_string()),
+            "display.hide_empty_entries" => Some(self.display.hide_empty_entries.to_string()),
+            "display.first_weekday" => Some(self.display.first_weekday.to_string()),
+            "theme.timestamp" => Some(self.theme.timestamp.clone()),
+            "theme.date_line" => Some(self.theme.date_line.clone()),
+            "theme.search_match" => Some(self.theme.search_match.clone()),
+            "theme.error" => Some(self.theme.error.clone()),
             _ => None,
         }
     }
 
-    pub fn create_config_file_if_not_exists() -> Result<(), Error> {
+    /// Creates the default config file if it doesn't exist. The path of the created
+    /// file is printed unless `quiet` is true.
+    pub fn create_config_file_if_not_exists(quiet: bool) -> Result<(), Error> {
         let config_dir_path: PathBuf = match config_dir_path() {
             Some(path) => path,
             None => return Err(Error::DetermineConfigDir),
@@ -37,70 +349,142 @@ impl Config {
         if config_file_path.exists() {
             return Ok(());
         }
-        create_dir_all(config_dir_path).expect("Can't create config file");
-        let file = File::create(&config_file_path).expect("Can't create config file");
-        Config::default().write_to_file(&file)?;
-        println!(
-            "Created the config file in `{}`",
-            config_file_path.display()
-        );
+        create_default_config_file(&config_file_path)?;
+        if !quiet {
+            println!(
+                "Created the config file in `{}`",
+                config_file_path.display()
+            );
+        }
         Ok(())
     }
 
     pub fn from_config_file<P: AsRef<Path>>(file_path: P) -> Result<Config, Error> {
-        let mut file = File::open(file_path).map_err(Error::Io)?;
+        let mut file = File::open(&file_path).map_err(Error::Io)?;
         let mut content = String::new();
         file.read_to_string(&mut content).map_err(Error::Io)?;
-        toml::from_str(&content).map_err(|error| {
-            Error::DeserializeConfigFile(error::DeserializeError::TomlError(error))
+        toml::from_str(&content).map_err(|error| Error::InvalidConfigFile {
+            path: file_path.as_ref().to_path_buf(),
+            source: Box::new(error::DeserializeError::TomlError(error)),
         })
     }
 
     pub fn write_to_file(&self, mut file: &File) -> Result<(), Error> {
-        let content = toml::to_string_pretty(self).map_err(Error::SerializeConfigFile)?;
+        let content = self.to_toml_string()?;
         file.write_all(content.as_bytes()).map_err(Error::Io)
     }
+
+    /// Serializes the config as it's written to the config file, with every key.
+    pub fn to_toml_string(&self) -> Result<String, Error> {
+        toml::to_string_pretty(self).map_err(Error::SerializeConfigFile)
+    }
+}
+
+/// Writes the default config to `config_file_path`, creating its directory if needed.
+fn create_default_config_file(config_file_path: &Path) -> Result<(), Error> {
+    let create_error = |source| Error::CreateConfigFile {
+        path: config_file_path.to_path_buf(),
+        source,
+    };
+    if let Some(config_dir_path) = config_file_path.parent() {
+        create_dir_all(config_dir_path).map_err(create_error)?;
+    }
+    let file = File::create(config_file_path).map_err(create_error)?;
+    Config::default().write_to_file(&file)
 }
 
 /// This function updates a specific key in a TOML file with a new value.
 /// It reads the entire file, parses it as TOML, updates the value of the given key,
 /// and then writes the modified TOML back to the file.
+///
+/// Keys missing from the file are inserted. The value is stored as a TOML value
+/// (e.g. a boolean or an integer) if the key accepts it, otherwise as a string.
 pub fn set_by_key(config_file_path: &Path, key: &str, value: String) -> Result<(), Error> {
+    if Config::default().get_by_key(key).is_none() {
+        return Err(Error::InvalidKey(key.to_owned()));
+    }
     let file_content = std::fs::read_to_string(config_file_path).map_err(Error::Io)?;
     let mut toml_doc = file_content
         .parse::<toml_edit::DocumentMut>()
         .map_err(|error| {
             Error::DeserializeConfigFile(error::DeserializeError::TomlEditError(error))
         })?;
-    let mut current_toml_node_opt: Option<&mut toml_edit::Item> = None;
-    for key_part in key.split('.') {
-        let new_node: &mut toml_edit::Item;
-        if let Some(current_toml_node) = current_toml_node_opt {
-            new_node = &mut current_toml_node[key_part];
-        } else {
-            new_node = &mut toml_doc[key_part];
-        }
-        if new_node.is_none() {
-            return Err(Error::InvalidKey(key.to_owned()));
+
+    let candidates = value
+        .parse::<toml_edit::Value>()
+        .into_iter()
+        .chain(std::iter::once(toml_edit::Value::from(value.as_str())));
+    for candidate in candidates {
+        *toml_item_by_key_mut(&mut toml_doc, key) = toml_edit::value(candidate);
+        if toml::from_str::<Config>(&toml_doc.to_string()).is_err() {
+            continue;
         }
-        current_toml_node_opt = Some(new_node);
+
+        return write_toml_doc(config_file_path, &toml_doc);
     }
-    if let Some(current_toml_node) = current_toml_node_opt {
-        *current_toml_node = toml_edit::value(value);
-        let mut config_file = File::create(config_file_path).map_err(Error::Io)?;
 
-        // Write the updated TOML content back to the config_file.
-        config_file
-            .write_all(toml_doc.to_string().as_bytes())
-            .map_err(Error::Io)?;
+    Err(Error::InvalidValue {
+        key: key.to_owned(),
+        value,
+    })
+}
 
-        // Ensure all buffered writes are written to the file.
-        config_file.flush().map_err(Error::Io)?;
-    } else {
-        return Err(Error::InvalidKey(key.to_string()));
+/// Removes `key` from the config file, so that it has its default value when the file is
+/// loaded again. Nothing is changed if the key isn't in the file.
+pub fn unset_by_key(config_file_path: &Path, key: &str) -> Result<(), Error> {
+    if Config::default().get_by_key(key).is_none() {
+        return Err(Error::InvalidKey(key.to_owned()));
+    }
+    let file_content = std::fs::read_to_string(config_file_path).map_err(Error::Io)?;
+    let mut toml_doc = file_content
+        .parse::<toml_edit::DocumentMut>()
+        .map_err(|error| {
+            Error::DeserializeConfigFile(error::DeserializeError::TomlEditError(error))
+        })?;
+
+    let (table_key, item_key) = key.rsplit_once('.').unwrap_or(("", key));
+    let mut table = Some(toml_doc.as_table_mut() as &mut dyn toml_edit::TableLike);
+    for key_part in table_key.split('.').filter(|key_part| !key_part.is_empty()) {
+        table = table
+            .and_then(|table| table.get_mut(key_part))
+            .and_then(toml_edit::Item::as_table_like_mut);
     }
+    if table.and_then(|table| table.remove(item_key)).is_none() {
+        return Ok(());
+    }
+
+    toml::from_str::<Config>(&toml_doc.to_string())
+        .map_err(|error| Error::DeserializeConfigFile(error::DeserializeError::TomlError(error)))?;
+    write_toml_doc(config_file_path, &toml_doc)
+}
+
+fn write_toml_doc(config_file_path: &Path, toml_doc: &toml_edit::DocumentMut) -> Result<(), Error> {
+    let mut config_file = File::create(config_file_path).map_err(Error::Io)?;
+
+    // Write the updated TOML content back to the config_file.
+    config_file
+        .write_all(toml_doc.to_string().as_bytes())
+        .map_err(Error::Io)?;
 
-    Ok(())
+    // Ensure all buffered writes are written to the file.
+    config_file.flush().map_err(Error::Io)
+}
+
+/// Returns the item of a dotted `key` in `toml_doc`, creating the missing tables on the way.
+fn toml_item_by_key_mut<'a>(
+    toml_doc: &'a mut toml_edit::DocumentMut,
+    key: &str,
+) -> &'a mut toml_edit::Item {
+    let mut key_parts = key.split('.').peekable();
+    let mut current_toml_node = toml_doc.as_item_mut();
+    while let Some(key_part) = key_parts.next() {
+        let new_node = &mut current_toml_node[key_part];
+        if new_node.is_none() && key_parts.peek().is_some() {
+            *new_node = toml_edit::table();
+        }
+        current_toml_node = new_node;
+    }
+    current_toml_node
 }
 
 pub fn construct_log_file_path(log_dir_path: &Path, date: &NaiveDate) -> PathBuf {
@@ -119,6 +503,99 @@ pub fn get_date_from_log_file_name(file_name: &str) -> Option<NaiveDate> {
     }
 }
 
+/// Returns the dates of all log files in `log_dir_path` in ascending order.
+pub fn get_all_log_dates(log_dir_path: &Path) -> std::io::Result<Vec<NaiveDate>> {
+    let mut ret = Vec::new();
+    for entry in std::fs::read_dir(log_dir_path)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        // Skip the files that aren't log files, including the ones with non-UTF-8 names
+        if let Some(date) = file_name.to_str().and_then(get_date_from_log_file_name) {
+            ret.push(date);
+        }
+    }
+    ret.sort();
+
+    Ok(ret)
+}
+
+/// Returns the first path of `<config file>.bak`, `<config file>.bak.1`, ... that doesn't exist.
+fn config_backup_file_path(config_file_path: &Path) -> PathBuf {
+    let mut file_name = config_file_path.file_name().unwrap_or_default().to_owned();
+    file_name.push(".bak");
+    let mut backup_file_path = config_file_path.with_file_name(&file_name);
+    for i in 1.. {
+        if !backup_file_path.exists() {
+            break;
+        }
+        let mut numbered_file_name = file_name.clone();
+        numbered_file_name.push(format!(".{}", i));
+        backup_file_path = config_file_path.with_file_name(numbered_file_name);
+    }
+    backup_file_path
+}
+
+/// Renames the config file at `config_file_path` to a backup file and writes the default
+/// config in its place, if it can't be loaded. Returns the path of the backup file,
+/// or None if the config file is fine.
+pub fn repair_config_file(config_file_path: &Path) -> Result<Option<PathBuf>, Error> {
+    match Config::from_config_file(config_file_path) {
+        Err(Error::InvalidConfigFile { .. }) => {}
+        Err(error) => return Err(error),
+        Ok(_) => return Ok(None),
+    }
+    let backup_file_path = config_backup_file_path(config_file_path);
+    std::fs::rename(config_file_path, &backup_file_path).map_err(Error::Io)?;
+    let file = File::create(config_file_path).map_err(Error::Io)?;
+    Config::default().write_to_file(&file)?;
+    Ok(Some(backup_file_path))
+}
+
+/// Returns the dates of all log files in `log_dir_path` in ascending order,
+/// without today's if `exclude_today` is true.
+pub fn get_log_dates(log_dir_path: &Path, exclude_today: bool) -> std::io::Result<Vec<NaiveDate>> {
+    let mut dates = get_all_log_dates(log_dir_path)?;
+    if exclude_today {
+        let today = get_today_date();
+        dates.retain(|date| *date != today);
+    }
+    Ok(dates)
+}
+
+/// Reads the log items of all log files in `log_dir_path` in ascending order of date,
+/// without today's if `exclude_today` is true.
+pub fn read_all_log_item_lists(
+    log_dir_path: &Path,
+    exclude_today: bool,
+) -> std::io::Result<Vec<(NaiveDate, LogItemList)>> {
+    let mut ret = Vec::new();
+    for date in get_log_dates(log_dir_path, exclude_today)? {
+        let file_path = construct_log_file_path(log_dir_path, &date);
+        ret.push((date, LogItemList::from_log_file(&file_path)?));
+    }
+
+    Ok(ret)
+}
+
+/// Returns an iterator over the log items of all log files in `log_dir_path` in ascending
+/// order of date. Unlike `read_all_log_item_lists`, only one log file is read at a time.
+pub fn entries(log_dir_path: &Path) -> impl Iterator<Item = std::io::Result<LogItem>> + '_ {
+    let (dates, error) = match get_all_log_dates(log_dir_path) {
+        Ok(dates) => (dates, None),
+        Err(error) => (Vec::new(), Some(error)),
+    };
+    let log_items = dates.into_iter().flat_map(move |date| {
+        let file_path = construct_log_file_path(log_dir_path, &date);
+        let log_items: Box<dyn Iterator<Item = std::io::Result<LogItem>>> =
+            match LogItemList::from_log_file(&file_path) {
+                Ok(log_item_list) => Box::new(log_item_list.into_iter().map(Ok)),
+                Err(error) => Box::new(std::iter::once(Err(error))),
+            };
+        log_items
+    });
+    error.map(Err).into_iter().chain(log_items)
+}
+
 pub fn config_dir_path() -> Option<PathBuf> {
     Some(
         std::env::var(CONFIG_DIR_ENV_VAR)
@@ -127,13 +604,37 @@ pub fn config_dir_path() -> Option<PathBuf> {
     )
 }
 
+/// The log directory: the environment variable `LOG_DIR_ENV_VAR` if it is set and not empty,
+/// otherwise `log.dir` of the config.
+/// The path is canonicalized if the directory exists, so a symlinked directory is resolved
+/// once and every log file path is built from the real directory.
+pub fn log_dir_path(log_config: &LogConfig) -> PathBuf {
+    let log_dir_path = std::env::var_os(LOG_DIR_ENV_VAR)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(&log_config.dir));
+    std::fs::canonicalize(&log_dir_path).unwrap_or(log_dir_path)
+}
+
 pub fn config_file_path(config_dir_path: &Path) -> PathBuf {
     config_dir_path.join("conf.toml")
 }
 
 #[cfg(test)]
 mod test {
-    use crate::config::Config;
+    use std::io::Write;
+
+    use chrono::NaiveDate;
+
+    use crate::{
+        config::{
+            construct_log_file_path, create_default_config_file, entries, get_all_log_dates,
+            log_dir_path, read_all_log_item_lists, repair_config_file, set_by_key, unset_by_key,
+            Config, FirstWeekday, LogConfig,
+        },
+        constants::{CONFIG_DIR_ENV_VAR, LOG_DIR_ENV_VAR},
+        utils::time::get_today_date,
+    };
 
     #[test]
     fn test_loading_and_generating_config_file() {
@@ -150,4 +651,224 @@ mod test {
         );
         std::fs::remove_file(&file_path).expect("Unable to delete the file");
     }
+
+    #[test]
+    fn test_set_by_key() {
+        let (mut test_config_file, file_path) = crate::utils::fs::create_unique_temp_file();
+        // A config file written before `log.strip_comments` existed
+        test_config_file
+            .write_all(b"[log]\ndir = \"/var/log/mylog\"\n")
+            .expect("should write the config file");
+        std::mem::drop(test_config_file);
+
+        let config = Config::from_config_file(&file_path).expect("should load the config file");
+        assert!(config.log.strip_comments);
+
+        set_by_key(&file_path, "log.strip_comments", "false".to_owned())
+            .expect("should set a boolean key");
+        set_by_key(&file_path, "log.dir", "2024".to_owned()).expect("should set a string key");
+        let config = Config::from_config_file(&file_path).expect("should load the config file");
+        assert!(!config.log.strip_comments);
+        assert_eq!(config.log.dir, "2024");
+
+        set_by_key(
+            &file_path,
+            "log.date_input_formats",
+            r#"["%d/%m/%Y", "%d/%m"]"#.to_owned(),
+        )
+        .expect("should set an array key");
+        let config = Config::from_config_file(&file_path).expect("should load the config file");
+        assert_eq!(config.log.date_input_formats, ["%d/%m/%Y", "%d/%m"]);
+
+        set_by_key(&file_path, "display.first_weekday", "sunday".to_owned())
+            .expect("should set an enum key");
+        let config = Config::from_config_file(&file_path).expect("should load the config file");
+        assert_eq!(config.display.first_weekday, FirstWeekday::Sunday);
+        assert!(set_by_key(&file_path, "display.first_weekday", "friday".to_owned()).is_err());
+
+        assert!(set_by_key(&file_path, "log.strip_comments", "maybe".to_owned()).is_err());
+        assert!(set_by_key(&file_path, "log.unknown", "1".to_owned()).is_err());
+        std::fs::remove_file(&file_path).expect("Unable to delete the file");
+    }
+
+    #[test]
+    fn test_unset_by_key() {
+        let (mut test_config_file, file_path) = crate::utils::fs::create_unique_temp_file();
+        test_config_file
+            .write_all(b"[log]\ndir = \"/var/log/mylog\"\nstrip_comments = false\n")
+            .expect("should write the config file");
+        std::mem::drop(test_config_file);
+
+        unset_by_key(&file_path, "log.dir").expect("should unset a key");
+        let config = Config::from_config_file(&file_path).expect("should load the config file");
+        assert_eq!(config.log.dir, "");
+        assert!(!config.log.strip_comments);
+
+        // Unsetting a key that isn't in the file changes nothing
+        unset_by_key(&file_path, "display.first_weekday").expect("should unset a missing key");
+        unset_by_key(&file_path, "log.strip_comments").expect("should unset a key");
+        let config = Config::from_config_file(&file_path).expect("should load the config file");
+        assert_eq!(config, Config::default());
+
+        assert!(matches!(
+            unset_by_key(&file_path, "log.nonexistent"),
+            Err(crate::Error::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_create_default_config_file() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let config_file_path = config_dir.path().join("mylog").join("conf.toml");
+        create_default_config_file(&config_file_path).unwrap();
+        assert_eq!(
+            Config::from_config_file(&config_file_path).unwrap(),
+            Config::default()
+        );
+
+        // The config directory can't be created under a file
+        let blocking_file_path = config_dir.path().join("file");
+        std::fs::write(&blocking_file_path, "").unwrap();
+        let config_file_path = blocking_file_path.join("mylog").join("conf.toml");
+        let error = create_default_config_file(&config_file_path).unwrap_err();
+        assert!(
+            matches!(&error, crate::Error::CreateConfigFile { path, .. } if *path == config_file_path)
+        );
+        assert!(error.to_string().contains(CONFIG_DIR_ENV_VAR));
+    }
+
+    #[test]
+    fn test_repair_config_file() {
+        let config_dir = tempfile::tempdir().unwrap();
+        let config_file_path = config_dir.path().join("conf.toml");
+        std::fs::write(&config_file_path, "[log]\ndir = \"/var/log/mylog\"\n").unwrap();
+        assert_eq!(repair_config_file(&config_file_path).unwrap(), None);
+
+        std::fs::write(&config_file_path, "[log\n").unwrap();
+        assert!(matches!(
+            Config::from_config_file(&config_file_path),
+            Err(crate::Error::InvalidConfigFile { .. })
+        ));
+        let backup_file_path = repair_config_file(&config_file_path).unwrap().unwrap();
+        assert_eq!(backup_file_path, config_dir.path().join("conf.toml.bak"));
+        assert_eq!(
+            std::fs::read_to_string(&backup_file_path).unwrap(),
+            "[log\n"
+        );
+        assert_eq!(
+            Config::from_config_file(&config_file_path).unwrap(),
+            Config::default()
+        );
+
+        // An existing backup isn't overwritten
+        std::fs::write(&config_file_path, "[log\n").unwrap();
+        assert_eq!(
+            repair_config_file(&config_file_path).unwrap(),
+            Some(config_dir.path().join("conf.toml.bak.1"))
+        );
+    }
+
+    #[test]
+    fn test_get_all_log_dates_skips_other_files() {
+        let log_dir = tempfile::tempdir().unwrap();
+        std::fs::write(log_dir.path().join("2025-01-06.log"), "").unwrap();
+        std::fs::write(log_dir.path().join("2025-13-45.log"), "").unwrap();
+        std::fs::write(log_dir.path().join("notes.txt"), "").unwrap();
+        #[cfg(unix)]
+        {
+            use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+            let file_name = OsStr::from_bytes(b"\xff\xfe.log");
+            std::fs::write(log_dir.path().join(file_name), "").unwrap();
+        }
+
+        assert_eq!(
+            get_all_log_dates(log_dir.path()).unwrap(),
+            [NaiveDate::from_ymd_opt(2025, 1, 6).unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_entries() {
+        let log_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            log_dir.path().join("2024-05-02.log"),
+            "[2024-05-02 08:00] third\n",
+        )
+        .unwrap();
+        std::fs::write(
+            log_dir.path().join("2024-05-01.log"),
+            "[2024-05-01 08:00] first\n[2024-05-01 09:00] second\n",
+        )
+        .unwrap();
+        std::fs::write(log_dir.path().join("2024-05-03.log"), "").unwrap();
+
+        let contents: Vec<String> = entries(log_dir.path())
+            .map(|log_item| log_item.unwrap().content().to_owned())
+            .collect();
+        assert_eq!(contents, ["first", "second", "third"]);
+
+        let missing_dir = log_dir.path().join("missing");
+        let mut missing_entries = entries(&missing_dir);
+        assert!(missing_entries.next().unwrap().is_err());
+        assert!(missing_entries.next().is_none());
+    }
+
+    #[test]
+    fn test_read_all_log_item_lists_exclude_today() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let today = get_today_date();
+        let yesterday = today.pred_opt().unwrap();
+        for date in [yesterday, today] {
+            let content = format!("[{} 12:00] entry\n", date);
+            std::fs::write(construct_log_file_path(log_dir.path(), &date), content).unwrap();
+        }
+
+        let dates = |exclude_today| -> Vec<NaiveDate> {
+            read_all_log_item_lists(log_dir.path(), exclude_today)
+                .unwrap()
+                .into_iter()
+                .map(|(date, _)| date)
+                .collect()
+        };
+        assert_eq!(dates(false), [yesterday, today]);
+        assert_eq!(dates(true), [yesterday]);
+    }
+
+    #[test]
+    fn test_log_dir_path() {
+        let log_config = LogConfig {
+            dir: "/from/config".to_owned(),
+            ..LogConfig::default()
+        };
+        // No other test reads the variable
+        std::env::set_var(LOG_DIR_ENV_VAR, "/from/env");
+        assert_eq!(log_dir_path(&log_config).to_str(), Some("/from/env"));
+        std::env::set_var(LOG_DIR_ENV_VAR, "");
+        assert_eq!(log_dir_path(&log_config).to_str(), Some("/from/config"));
+        std::env::remove_var(LOG_DIR_ENV_VAR);
+        assert_eq!(log_dir_path(&log_config).to_str(), Some("/from/config"));
+
+        // A symlinked log directory is resolved to the real one
+        #[cfg(unix)]
+        {
+            let dir = tempfile::tempdir().unwrap();
+            let real_log_dir_path = dir.path().join("journal");
+            let link_path = dir.path().join("link");
+            std::fs::create_dir(&real_log_dir_path).unwrap();
+            std::os::unix::fs::symlink(&real_log_dir_path, &link_path).unwrap();
+            let date = NaiveDate::from_ymd_opt(2024, 5, 6).unwrap();
+            std::fs::write(construct_log_file_path(&link_path, &date), "").unwrap();
+
+            let log_config = LogConfig {
+                dir: link_path.to_str().unwrap().to_owned(),
+                ..LogConfig::default()
+            };
+            let log_dir_path = log_dir_path(&log_config);
+            assert_eq!(
+                log_dir_path,
+                std::fs::canonicalize(&real_log_dir_path).unwrap()
+            );
+            assert_eq!(get_all_log_dates(&log_dir_path).unwrap(), [date]);
+        }
+    }
 }
@@ -1,5 +1,11 @@
 pub const CONFIG_DIR_ENV_VAR: &str = "MYLOG_CONFIG_DIR";
 
+/// Overrides `log.dir` without changing the config file.
+pub const LOG_DIR_ENV_VAR: &str = "MYLOG_DIR";
+
 pub const PKG_NAME: &str = std::env!("CARGO_PKG_NAME");
 
 pub const PKG_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The read-only configuration key whose value is the path of the config file.
+pub const CONFIG_PATH_KEY: &str = "config.path";
@@ -0,0 +1,131 @@
+use std::{io::Write, path::Path};
+
+use chrono::NaiveDate;
+use crossterm::style::Stylize;
+use similar::TextDiff;
+
+use crate::{config::construct_log_file_path, error::Error, utils::output::Output};
+
+/// The number of unchanged lines shown before and after each change.
+const CONTEXT_LINES: usize = 3;
+
+/// Returns the hunks of the unified diff from `old` to `new`, without the file headers.
+/// Returns no lines if they are the same.
+pub fn unified_diff(old: &str, new: &str) -> Vec<String> {
+    let text_diff = TextDiff::from_lines(old, new);
+    let mut lines = Vec::new();
+    for hunk in text_diff
+        .unified_diff()
+        .context_radius(CONTEXT_LINES)
+        .iter_hunks()
+    {
+        lines.push(hunk.header().to_string());
+        for change in hunk.iter_changes() {
+            let line = change.value().trim_end_matches(['\r', '\n']);
+            lines.push(format!("{}{}", change.tag(), line));
+        }
+    }
+    lines
+}
+
+/// Reads the log file of `date`, which is empty if it doesn't exist.
+fn read_log_file(log_dir_path: &Path, date: &NaiveDate) -> Result<String, Error> {
+    let file_path = construct_log_file_path(log_dir_path, date);
+    match std::fs::read_to_string(&file_path) {
+        Ok(content) => Ok(content),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(error) => Err(Error::from_io(error, &file_path)),
+    }
+}
+
+/// Prints the unified diff from the log file of `old_date` to the one of `new_date`,
+/// colored if stdout is a terminal. Nothing is printed if they are the same.
+pub fn print_diff(
+    log_dir_path: &Path,
+    old_date: NaiveDate,
+    new_date: NaiveDate,
+) -> Result<(), Error> {
+    if !log_dir_path.exists() {
+        return Err(Error::LogDirNotFound(log_dir_path.to_path_buf()));
+    }
+
+    let hunk_lines = unified_diff(
+        &read_log_file(log_dir_path, &old_date)?,
+        &read_log_file(log_dir_path, &new_date)?,
+    );
+    if hunk_lines.is_empty() {
+        return Ok(());
+    }
+
+    let mut output = Output::new(None).map_err(Error::Io)?;
+    let colored = output.is_terminal();
+    let header_lines = [format!("--- {}", old_date), format!("+++ {}", new_date)];
+    for line in header_lines {
+        if colored {
+            writeln!(output, "{}", line.bold()).map_err(Error::Io)?;
+        } else {
+            writeln!(output, "{}", line).map_err(Error::Io)?;
+        }
+    }
+    for line in hunk_lines {
+        if !colored {
+            writeln!(output, "{}", line).map_err(Error::Io)?;
+            continue;
+        }
+        let line = match line.chars().next() {
+            Some('-') => line.red(),
+            Some('+') => line.green(),
+            Some('@') => line.cyan(),
+            _ => line.stylize(),
+        };
+        writeln!(output, "{}", line).map_err(Error::Io)?;
+    }
+    output.flush().map_err(Error::Io)
+}
+
+#[cfg(test)]
+mod test {
+    use super::unified_diff;
+
+    #[test]
+    fn test_unified_diff() {
+        assert!(unified_diff("a\nb\n", "a\nb\n").is_empty());
+        assert_eq!(unified_diff("", "a\nb\n"), ["@@ -0,0 +1,2 @@", "+a", "+b"]);
+        assert_eq!(unified_diff("a\n", ""), ["@@ -1 +0,0 @@", "-a"]);
+
+        let lines = |changed_lines: &[(usize, &str)]| -> String {
+            (1..=12)
+                .map(
+                    |i| match changed_lines.iter().find(|(line, _)| *line == i) {
+                        Some((_, changed_line)) => format!("{}\n", changed_line),
+                        None => format!("{}\n", i),
+                    },
+                )
+                .collect()
+        };
+        let old = lines(&[]);
+        let new = lines(&[(2, "two"), (11, "eleven")]);
+        assert_eq!(
+            unified_diff(&old, &new),
+            [
+                "@@ -1,5 +1,5 @@",
+                " 1",
+                "-2",
+                "+two",
+                " 3",
+                " 4",
+                " 5",
+                "@@ -8,5 +8,5 @@",
+                " 8",
+                " 9",
+                " 10",
+                "-11",
+                "+eleven",
+                " 12",
+            ]
+        );
+        // The changes with no more than twice the context between them share a hunk
+        let new = lines(&[(2, "two"), (9, "nine")]);
+        assert_eq!(unified_diff(&old, &new)[0], "@@ -1,12 +1,12 @@");
+    }
+}
@@ -1,4 +1,7 @@
-use std::{fmt::Display, path::PathBuf};
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
 use crate::constants::{CONFIG_DIR_ENV_VAR, PKG_NAME};
 
@@ -32,13 +35,63 @@ pub enum Error {
     DateParse {
         input: String,
         source: chrono::ParseError,
+        formats: Vec<String>,
     },
     Io(std::io::Error),
     InvalidKey(String),
+    ReadOnlyKey(String),
+    InvalidValue {
+        key: String,
+        value: String,
+    },
     EmptyLogMessage,
     SerializeConfigFile(toml::ser::Error),
     DeserializeConfigFile(DeserializeError),
     DetermineConfigDir,
+    InvalidPattern(regex::Error),
+    LogItemNotFound(String),
+    /// The ID to find a log item by is empty, which every ID would start with
+    EmptyLogItemId,
+    NoEditor,
+    /// The program of `log.editor` isn't found
+    EditorNotFound(String),
+    LogMessageTooLong {
+        chars: usize,
+        max_entry_chars: usize,
+    },
+    DeserializeImportFile(serde_json::Error),
+    ConfigFileNotFound(PathBuf),
+    FutureDate(chrono::NaiveDate),
+    /// The config file can't be loaded, which `config --repair` fixes
+    InvalidConfigFile {
+        path: PathBuf,
+        source: Box<DeserializeError>,
+    },
+    /// The archive or an archived log file would be overwritten
+    ArchiveTargetExists(PathBuf),
+    /// The backup file doesn't have the `.zip` extension
+    NotAZipFile(PathBuf),
+    /// A log file or the log directory can't be read or written
+    PermissionDenied(PathBuf),
+    /// A log file to rewrite has text before its first log item, which would be lost
+    LeadingText(PathBuf),
+    /// The log item to delete isn't in the log file anymore, e.g. after an external edit
+    LogFileChanged(PathBuf),
+    /// The default config file or its directory can't be created
+    CreateConfigFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+}
+
+impl Error {
+    /// Wraps `error` from accessing `path`, naming the path if the permission is denied.
+    pub fn from_io(error: std::io::Error, path: &Path) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::PermissionDenied => Self::PermissionDenied(path.to_path_buf()),
+            _ => Self::Io(error),
+        }
+    }
 }
 
 impl std::error::Error for Error {
@@ -48,6 +101,10 @@ impl std::error::Error for Error {
             Self::Io(err) => Some(err),
             Self::SerializeConfigFile(source) => Some(source),
             Self::DeserializeConfigFile(source) => Some(source),
+            Self::InvalidPattern(source) => Some(source),
+            Self::DeserializeImportFile(source) => Some(source),
+            Self::InvalidConfigFile { source, .. } => Some(source.as_ref()),
+            Self::CreateConfigFile { source, .. } => Some(source),
             _ => None,
         }
     }
@@ -65,8 +122,18 @@ impl Display for Error {
                     pkg_name
                 )
             }
-            Self::DateParse { input, source } => {
-                write!(f, "invalid date '{}': {}", input, source)
+            Self::DateParse {
+                input,
+                source,
+                formats,
+            } => {
+                write!(
+                    f,
+                    "invalid date '{}': {} (tried formats: {})",
+                    input,
+                    source,
+                    formats.join(", ")
+                )
             }
             Self::Io(io_error) => {
                 write!(f, "error: {}", io_error)
@@ -74,6 +141,12 @@ impl Display for Error {
             Self::InvalidKey(key) => {
                 write!(f, "error: invalid key: `{}`", key)
             }
+            Self::ReadOnlyKey(key) => {
+                write!(f, "error: `{}` is read-only", key)
+            }
+            Self::InvalidValue { key, value } => {
+                write!(f, "error: invalid value for `{}`: `{}`", key, value)
+            }
             Self::EmptyLogMessage => {
                 write!(f, "Aborting due to empty log message.")
             }
@@ -90,6 +163,117 @@ impl Display for Error {
             Self::DetermineConfigDir => {
                 write!(f, "error: could not determine the config directory\nTry setting the environment variable `{}` to customize your configuration directory.", CONFIG_DIR_ENV_VAR)
             }
+            Self::InvalidPattern(source) => {
+                write!(f, "error: invalid pattern: {}", source)
+            }
+            Self::LogItemNotFound(id) => {
+                write!(f, "error: no log message with the ID `{}`", id)
+            }
+            Self::EmptyLogItemId => {
+                write!(f, "error: the ID of the log message is empty")
+            }
+            Self::NoEditor => {
+                write!(
+                    f,
+                    "error: no editor found, set `log.editor` by running `{} config log.editor <your-editor>`, or the environment variable `EDITOR` or `VISUAL`",
+                    PKG_NAME
+                )
+            }
+            Self::LogFileChanged(path) => {
+                write!(
+                    f,
+                    "error: '{}' has changed since it was shown, so nothing is deleted",
+                    path.display()
+                )
+            }
+            Self::LeadingText(path) => {
+                write!(
+                    f,
+                    "error: '{}' has text before the first log message, which would be lost by rewriting it",
+                    path.display()
+                )
+            }
+            Self::EditorNotFound(program) => {
+                write!(
+                    f,
+                    "error: the editor `{}` of `log.editor` isn't found, check that it's installed and in `PATH`",
+                    program
+                )
+            }
+            Self::LogMessageTooLong {
+                chars,
+                max_entry_chars,
+            } => {
+                write!(
+                    f,
+                    "error: the log message has {} characters, more than `log.max_entry_chars` ({})",
+                    chars, max_entry_chars
+                )
+            }
+            Self::DeserializeImportFile(source) => {
+                write!(f, "error: fail to deserialize the import file: {}", source)
+            }
+            Self::ConfigFileNotFound(config_file_path) => {
+                write!(
+                    f,
+                    "error: the config file '{}' doesn't exist",
+                    config_file_path.display()
+                )
+            }
+            Self::FutureDate(date) => {
+                write!(f, "error: can't write to a future date: {}", date)
+            }
+            Self::InvalidConfigFile { path, source } => {
+                write!(
+                    f,
+                    "error: fail to deserialize the config file '{}': {}\nYou can back it up and regenerate a default one by running `{} config --repair`.",
+                    path.display(),
+                    source,
+                    PKG_NAME
+                )
+            }
+            Self::ArchiveTargetExists(path) => {
+                write!(f, "error: '{}' already exists", path.display())
+            }
+            Self::NotAZipFile(path) => {
+                write!(f, "error: '{}' isn't a `.zip` file", path.display())
+            }
+            Self::PermissionDenied(path) => {
+                write!(
+                    f,
+                    "error: permission denied: '{}'\nCheck the owner and the permissions of it and its directory with `ls -l`, and fix them with `chown` or `chmod`.",
+                    path.display()
+                )
+            }
+            Self::CreateConfigFile { path, source } => {
+                write!(
+                    f,
+                    "error: can't create the config file '{}': {}\nTry setting the environment variable `{}` to a writable directory.",
+                    path.display(),
+                    source,
+                    CONFIG_DIR_ENV_VAR
+                )
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{io, path::Path};
+
+    use super::Error;
+
+    #[test]
+    fn test_from_io() {
+        let path = Path::new("/var/log/mylog/2024-05-06.log");
+        let error = Error::from_io(io::Error::from(io::ErrorKind::PermissionDenied), path);
+        assert!(matches!(&error, Error::PermissionDenied(error_path) if error_path == path));
+        assert!(error
+            .to_string()
+            .contains("'/var/log/mylog/2024-05-06.log'"));
+
+        let error = Error::from_io(io::Error::from(io::ErrorKind::NotFound), path);
+        assert!(matches!(error, Error::Io(_)));
+    }
+}
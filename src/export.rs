@@ -0,0 +1,186 @@
+use std::io::Write;
+use std::path::Path;
+
+use chrono::NaiveDate;
+
+use crate::cli::ExportFormat;
+use crate::config::read_all_log_item_lists;
+use crate::log_item::{LogItem, WordCountRange};
+
+#[derive(Clone, Copy, Debug)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    /// The first date to export, inclusive.
+    pub since: Option<NaiveDate>,
+    /// The last date to export, inclusive.
+    pub until: Option<NaiveDate>,
+    /// Writes the log items of all days as one chronological stream, without date headers.
+    pub flat: bool,
+    /// Leaves out today's log items.
+    pub exclude_today: bool,
+    /// Only exports the log items whose number of words is in the range.
+    pub word_count: WordCountRange,
+}
+
+/// Writes the log items in `log_dir_path` to `output` as configured by `options`.
+pub fn export_logs(
+    log_dir_path: &Path,
+    options: ExportOptions,
+    output: &mut impl Write,
+) -> std::io::Result<()> {
+    let log_item_lists = read_all_log_item_lists(log_dir_path, options.exclude_today)?
+        .into_iter()
+        .filter(|(date, _)| options.since.is_none_or(|since| *date >= since))
+        .filter(|(date, _)| options.until.is_none_or(|until| *date <= until))
+        .map(|(date, mut log_item_list)| {
+            log_item_list.retain(|log_item| options.word_count.contains(log_item));
+            (date, log_item_list)
+        });
+
+    if options.flat {
+        let mut log_items: Vec<LogItem> = log_item_lists
+            .flat_map(|(_, log_item_list)| log_item_list.into_iter())
+            .collect();
+        log_items.sort_by(|a, b| a.date_time().cmp(b.date_time()));
+        for log_item in log_items {
+            write!(output, "{}", log_item)?;
+        }
+        return output.flush();
+    }
+
+    let mut is_first_day = true;
+    for (date, log_item_list) in log_item_lists {
+        match options.format {
+            ExportFormat::Log => write!(output, "{}", log_item_list)?,
+            ExportFormat::Text => {
+                if log_item_list.is_empty() {
+                    continue;
+                }
+                if !is_first_day {
+                    writeln!(output)?;
+                }
+                writeln!(output, "===== {} =====", date)?;
+                write!(output, "{}", log_item_list)?;
+                is_first_day = false;
+            }
+        }
+    }
+    output.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+
+    use crate::{cli::ExportFormat, log_item::WordCountRange};
+
+    use super::{export_logs, ExportOptions};
+
+    #[test]
+    fn test_export_logs_as_text() {
+        let log_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            log_dir.path().join("2024-05-12.log"),
+            "[2024-05-12 08:00] first\n[2024-05-12 09:00] second\n",
+        )
+        .unwrap();
+        std::fs::write(log_dir.path().join("2024-05-13.log"), "").unwrap();
+        std::fs::write(
+            log_dir.path().join("2024-05-14.log"),
+            "[2024-05-14 10:00] third\n",
+        )
+        .unwrap();
+
+        let mut output: Vec<u8> = Vec::new();
+        let options = ExportOptions {
+            format: ExportFormat::Text,
+            since: None,
+            until: None,
+            flat: false,
+            exclude_today: false,
+            word_count: WordCountRange::default(),
+        };
+        export_logs(log_dir.path(), options, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "===== 2024-05-12 =====\n\
+             [2024-05-12 08:00] first\n\
+             [2024-05-12 09:00] second\n\
+             \n\
+             ===== 2024-05-14 =====\n\
+             [2024-05-14 10:00] third\n"
+        );
+    }
+
+    #[test]
+    fn test_export_logs_flat() {
+        let log_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            log_dir.path().join("2024-05-12.log"),
+            "[2024-05-12 09:00] second\n[2024-05-12 08:00] first\n",
+        )
+        .unwrap();
+        std::fs::write(
+            log_dir.path().join("2024-05-13.log"),
+            "[2024-05-13 10:00] third\n",
+        )
+        .unwrap();
+        std::fs::write(
+            log_dir.path().join("2024-05-14.log"),
+            "[2024-05-14 10:00] fourth\n",
+        )
+        .unwrap();
+
+        let mut output: Vec<u8> = Vec::new();
+        let options = ExportOptions {
+            format: ExportFormat::Text,
+            since: None,
+            until: NaiveDate::from_ymd_opt(2024, 5, 13),
+            flat: true,
+            exclude_today: false,
+            word_count: WordCountRange::default(),
+        };
+        export_logs(log_dir.path(), options, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "[2024-05-12 08:00] first\n\
+             [2024-05-12 09:00] second\n\
+             [2024-05-13 10:00] third\n"
+        );
+    }
+
+    #[test]
+    fn test_export_logs_by_word_count() {
+        let log_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            log_dir.path().join("2024-05-12.log"),
+            "[2024-05-12 08:00] short\n[2024-05-12 09:00] a longer one\n",
+        )
+        .unwrap();
+        std::fs::write(
+            log_dir.path().join("2024-05-13.log"),
+            "[2024-05-13 10:00] brief\n",
+        )
+        .unwrap();
+
+        let mut output: Vec<u8> = Vec::new();
+        let options = ExportOptions {
+            format: ExportFormat::Text,
+            since: None,
+            until: None,
+            flat: false,
+            exclude_today: false,
+            word_count: WordCountRange {
+                min: Some(2),
+                max: None,
+            },
+        };
+        export_logs(log_dir.path(), options, &mut output).unwrap();
+        // The day without a log item left isn't exported
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "===== 2024-05-12 =====\n\
+             [2024-05-12 09:00] a longer one\n"
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use std::{collections::BTreeMap, io::Write, path::Path};
+
+use chrono::NaiveDate;
+use crossterm::style::Stylize;
+use regex::{Regex, RegexBuilder};
+
+use crate::{
+    config::{construct_log_file_path, read_all_log_item_lists},
+    error::Error,
+    log_item::{LogItem, LogItemList, WordCountRange},
+    log_pager::search::mark_search_result,
+    theme::Theme,
+    utils::output::Output,
+};
+
+pub fn build_search_regex(pattern: &str, ignore_case: bool) -> Result<Regex, Error> {
+    RegexBuilder::new(pattern)
+        .case_insensitive(ignore_case)
+        .build()
+        .map_err(Error::InvalidPattern)
+}
+
+/// Returns the log items in `log_dir_path` whose content matches `regex` and whose
+/// number of words is in `word_count`, only searching the log file of `date` if it's given.
+pub fn grep_log_items(
+    log_dir_path: &Path,
+    regex: &Regex,
+    date: Option<NaiveDate>,
+    word_count: WordCountRange,
+) -> std::io::Result<Vec<LogItem>> {
+    let log_item_lists = match date {
+        Some(date) => {
+            let file_path = construct_log_file_path(log_dir_path, &date);
+            match LogItemList::from_log_file(&file_path) {
+                Ok(log_item_list) => vec![log_item_list],
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+                Err(error) => return Err(error),
+            }
+        }
+        None => read_all_log_item_lists(log_dir_path, false)?
+            .into_iter()
+            .map(|(_, log_item_list)| log_item_list)
+            .collect(),
+    };
+
+    Ok(log_item_lists
+        .into_iter()
+        .flat_map(LogItemList::into_iter)
+        .filter(|log_item| regex.is_match(log_item.content()) && word_count.contains(log_item))
+        .collect())
+}
+
+/// Writes the matched log items to `output`, highlighting the matches if `output` is a terminal.
+pub fn write_grep_result(
+    output: &mut Output,
+    regex: &Regex,
+    log_items: &[LogItem],
+    theme: &Theme,
+) -> std::io::Result<()> {
+    for log_item in log_items {
+        if output.is_terminal() {
+            let date_str = format!("[{}]", log_item.date_time().format("%Y-%m-%d %H:%M"));
+            writeln!(
+                output,
+                "{} {}",
+                date_str.with(theme.timestamp),
+                mark_search_result(regex, log_item.content(), theme.search_match)
+            )?;
+        } else {
+            write!(output, "{}", log_item)?;
+        }
+    }
+    output.flush()
+}
+
+/// Counts the log items of each date, in ascending order of date.
+pub fn count_log_items_by_day(log_items: &[LogItem]) -> Vec<(NaiveDate, usize)> {
+    let mut counts: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    for log_item in log_items {
+        *counts.entry(log_item.date_time().date()).or_default() += 1;
+    }
+    counts.into_iter().collect()
+}
+
+/// Writes the number of matched log items to `output`, or the number of each day if `by_day` is true.
+pub fn write_grep_count(
+    output: &mut Output,
+    log_items: &[LogItem],
+    by_day: bool,
+) -> std::io::Result<()> {
+    if by_day {
+        for (date, count) in count_log_items_by_day(log_items) {
+            writeln!(output, "{}: {}", date, count)?;
+        }
+    } else {
+        writeln!(output, "{}", log_items.len())?;
+    }
+    output.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+
+    use crate::log_item::WordCountRange;
+
+    use super::{build_search_regex, count_log_items_by_day, grep_log_items};
+
+    #[test]
+    fn test_grep_log_items() {
+        let log_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            log_dir.path().join("2024-05-01.log"),
+            "[2024-05-01 08:00] Deploy the app\n[2024-05-01 09:00] lunch\n",
+        )
+        .unwrap();
+        std::fs::write(
+            log_dir.path().join("2024-05-02.log"),
+            "[2024-05-02 08:00] deploy again\n",
+        )
+        .unwrap();
+
+        let regex = build_search_regex("deploy", false).unwrap();
+        let log_items =
+            grep_log_items(log_dir.path(), &regex, None, WordCountRange::default()).unwrap();
+        assert_eq!(log_items.len(), 1);
+        assert_eq!(log_items[0].content(), "deploy again");
+
+        let regex = build_search_regex("deploy", true).unwrap();
+        assert_eq!(
+            grep_log_items(log_dir.path(), &regex, None, WordCountRange::default())
+                .unwrap()
+                .len(),
+            2
+        );
+        let date = NaiveDate::from_ymd_opt(2024, 5, 1);
+        assert_eq!(
+            grep_log_items(log_dir.path(), &regex, date, WordCountRange::default())
+                .unwrap()
+                .len(),
+            1
+        );
+        let date = NaiveDate::from_ymd_opt(2024, 5, 3);
+        assert!(
+            grep_log_items(log_dir.path(), &regex, date, WordCountRange::default())
+                .unwrap()
+                .is_empty()
+        );
+
+        let log_items =
+            grep_log_items(log_dir.path(), &regex, None, WordCountRange::default()).unwrap();
+        assert_eq!(
+            count_log_items_by_day(&log_items),
+            [
+                (NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(), 1),
+                (NaiveDate::from_ymd_opt(2024, 5, 2).unwrap(), 1)
+            ]
+        );
+
+        assert!(build_search_regex("(", false).is_err());
+    }
+
+    #[test]
+    fn test_grep_log_items_by_word_count() {
+        let log_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            log_dir.path().join("2024-05-01.log"),
+            "[2024-05-01 08:00] deploy\n\
+             [2024-05-01 09:00] deploy the app\n\
+             [2024-05-01 10:00] deploy the app again\n\
+             [2024-05-01 11:00] lunch with the team\n",
+        )
+        .unwrap();
+
+        let regex = build_search_regex("deploy", false).unwrap();
+        let contents = |min, max| -> Vec<String> {
+            let word_count = WordCountRange { min, max };
+            grep_log_items(log_dir.path(), &regex, None, word_count)
+                .unwrap()
+                .iter()
+                .map(|log_item| log_item.content().to_owned())
+                .collect()
+        };
+        assert_eq!(
+            contents(Some(3), None),
+            ["deploy the app", "deploy the app again"]
+        );
+        assert_eq!(contents(None, Some(3)), ["deploy", "deploy the app"]);
+        assert_eq!(contents(Some(3), Some(3)), ["deploy the app"]);
+        // `lunch with the team` has 4 words, but doesn't match the pattern
+        assert_eq!(contents(Some(4), None), ["deploy the app again"]);
+        assert!(contents(Some(5), None).is_empty());
+    }
+}
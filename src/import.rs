@@ -0,0 +1,190 @@
+use std::{collections::BTreeMap, io::Read, path::Path, str::FromStr};
+
+use chrono::NaiveDate;
+
+use crate::{
+    cli::ImportFormat,
+    config::construct_log_file_path,
+    error::Error,
+    log_item::{LogItem, LogItemList},
+    tidy::{parse_log_content, warn_leading_text},
+    utils::fs::{open_locked, overwrite_file},
+};
+
+/// The number of log items imported into the log file of a date.
+#[derive(Debug, PartialEq)]
+pub struct ImportedDay {
+    pub date: NaiveDate,
+    pub imported: usize,
+    /// Log items that are already in the log file.
+    pub skipped: usize,
+}
+
+/// Reads the log items to import from the file at `file_path` in `format`.
+pub fn read_import_file(file_path: &Path, format: ImportFormat) -> Result<Vec<LogItem>, Error> {
+    let file_content = std::fs::read_to_string(file_path).map_err(Error::Io)?;
+    match format {
+        ImportFormat::Json => {
+            let log_items: Vec<LogItem> =
+                serde_json::from_str(&file_content).map_err(Error::DeserializeImportFile)?;
+            // Normalize the content the same way as the parsed log items
+            Ok(log_items
+                .into_iter()
+                .map(|log_item| LogItem::new(*log_item.date_time(), log_item.content()))
+                .collect())
+        }
+        ImportFormat::Log => Ok(LogItemList::from_str(&file_content)
+            .expect("Invalid log file")
+            .into_iter()
+            .collect()),
+    }
+}
+
+/// Merges the log items into the log files in `log_dir_path` in chronological order.
+/// Log items that already exist are skipped, and so are log files with text before the
+/// first log item, which would be lost by rewriting them. Nothing is written if `dry_run` is true.
+pub fn import_log_items(
+    log_dir_path: &Path,
+    log_items: Vec<LogItem>,
+    dry_run: bool,
+) -> std::io::Result<Vec<ImportedDay>> {
+    let mut log_items_by_date: BTreeMap<NaiveDate, Vec<LogItem>> = BTreeMap::new();
+    for log_item in log_items {
+        log_items_by_date
+            .entry(log_item.date_time().date())
+            .or_default()
+            .push(log_item);
+    }
+
+    let mut ret: Vec<ImportedDay> = Vec::new();
+    for (date, log_items) in log_items_by_date {
+        let log_file_path = construct_log_file_path(log_dir_path, &date);
+        // The log file stays locked while it's rewritten, so that no concurrent write is lost
+        let mut file = if dry_run {
+            None
+        } else {
+            Some(open_locked(&log_file_path)?)
+        };
+        let file_content = match &mut file {
+            Some(file) => {
+                let mut file_content = String::new();
+                file.read_to_string(&mut file_content)?;
+                file_content
+            }
+            None => match std::fs::read_to_string(&log_file_path) {
+                Ok(file_content) => file_content,
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+                Err(error) => return Err(error),
+            },
+        };
+        let Some(mut log_item_list) = parse_log_content(&file_content) else {
+            warn_leading_text(&log_file_path);
+            continue;
+        };
+
+        let count = log_items.len();
+        let imported = log_items
+            .into_iter()
+            .map(|log_item| log_item_list.insert_sorted(log_item))
+            .filter(|is_inserted| *is_inserted)
+            .count();
+        if let Some(file) = &mut file {
+            if imported > 0 {
+                overwrite_file(file, &log_item_list.to_string())?;
+            }
+        }
+        ret.push(ImportedDay {
+            date,
+            imported,
+            skipped: count - imported,
+        });
+    }
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+
+    use crate::cli::ImportFormat;
+
+    use super::{import_log_items, read_import_file, ImportedDay};
+
+    #[test]
+    fn test_import_log_items() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let log_file_path = log_dir.path().join("2024-05-01.log");
+        std::fs::write(
+            &log_file_path,
+            "[2024-05-01 08:00] first\n[2024-05-01 12:00] third\n",
+        )
+        .unwrap();
+
+        let import_file_path = log_dir.path().join("import.json");
+        std::fs::write(
+            &import_file_path,
+            r#"[
+                {"date_time": "2024-05-02 09:00", "content": "fourth"},
+                {"date_time": "2024-05-01T10:30:00", "content": " second "},
+                {"date_time": "2024-05-01 08:00", "content": "first"}
+            ]"#,
+        )
+        .unwrap();
+
+        let log_items = read_import_file(&import_file_path, ImportFormat::Json).unwrap();
+        let expected = [
+            ImportedDay {
+                date: NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+                imported: 1,
+                skipped: 1,
+            },
+            ImportedDay {
+                date: NaiveDate::from_ymd_opt(2024, 5, 2).unwrap(),
+                imported: 1,
+                skipped: 0,
+            },
+        ];
+
+        assert_eq!(
+            import_log_items(log_dir.path(), log_items, true).unwrap(),
+            expected
+        );
+        assert!(!log_dir.path().join("2024-05-02.log").exists());
+
+        let log_items = read_import_file(&import_file_path, ImportFormat::Json).unwrap();
+        assert_eq!(
+            import_log_items(log_dir.path(), log_items, false).unwrap(),
+            expected
+        );
+        assert_eq!(
+            std::fs::read_to_string(&log_file_path).unwrap(),
+            "[2024-05-01 08:00] first\n\
+             [2024-05-01 10:30] second\n\
+             [2024-05-01 12:00] third\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(log_dir.path().join("2024-05-02.log")).unwrap(),
+            "[2024-05-02 09:00] fourth\n"
+        );
+
+        // The log file would lose the text before the first log item
+        let content = "notes\n[2024-05-02 09:00] fourth\n";
+        std::fs::write(log_dir.path().join("2024-05-02.log"), content).unwrap();
+        let log_items = read_import_file(&import_file_path, ImportFormat::Json).unwrap();
+        assert_eq!(
+            import_log_items(log_dir.path(), log_items, false).unwrap(),
+            [ImportedDay {
+                date: NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+                imported: 0,
+                skipped: 2,
+            }]
+        );
+        assert_eq!(
+            std::fs::read_to_string(log_dir.path().join("2024-05-02.log")).unwrap(),
+            content
+        );
+
+        std::fs::write(&import_file_path, "not json").unwrap();
+        assert!(read_import_file(&import_file_path, ImportFormat::Json).is_err());
+    }
+}
@@ -1,23 +1,60 @@
-use std::str::FromStr;
+use std::{path::Path, str::FromStr};
 
-use chrono::NaiveDateTime;
+use chrono::{Datelike, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use crate::utils::template::expand_template;
 
 pub enum ParseError {
     DateNotFound,
 }
 
-#[derive(Debug, Default)]
+/// A log item is (de)serialized as `{"date_time": "%Y-%m-%d %H:%M", "content": "..."}`.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct LogItem {
+    #[serde(with = "date_time_format")]
     date_time: chrono::NaiveDateTime,
     content: String,
 }
 
+mod date_time_format {
+    use chrono::NaiveDateTime;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    const FORMAT: &str = "%Y-%m-%d %H:%M";
+    /// Also accepted when deserializing, since other tools often write ISO 8601 date times.
+    const ISO_8601_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+    pub fn serialize<S: Serializer>(
+        date_time: &NaiveDateTime,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&date_time.format(FORMAT).to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<NaiveDateTime, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        NaiveDateTime::parse_from_str(&s, FORMAT)
+            .or_else(|_| NaiveDateTime::parse_from_str(&s, ISO_8601_FORMAT))
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 impl std::fmt::Display for LogItem {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        // A content starting on the next line keeps the indentation of that line
+        let separator = if self.content.starts_with('\n') {
+            ""
+        } else {
+            " "
+        };
         let data = format!(
-            "[{}] {}\n",
+            "[{}]{}{}\n",
             self.date_time.format("%Y-%m-%d %H:%M"),
-            self.content
+            separator,
+            escape_content(&self.content)
         );
 
         fmt.write_str(&data)
@@ -41,7 +78,7 @@ impl FromStr for LogItem {
         let date_str = &s[1..idx];
         match chrono::NaiveDateTime::parse_from_str(date_str, "%Y-%m-%d %H:%M") {
             Ok(date_time_result) => {
-                let log_content = s[idx + 1..].to_owned();
+                let log_content = unescape_content(&s[idx + 1..]);
                 Ok(LogItem::new(date_time_result, &log_content))
             }
             Err(_) => Err(ParseError::DateNotFound),
@@ -53,7 +90,7 @@ impl LogItem {
     pub fn new(date_time: NaiveDateTime, content: &str) -> Self {
         Self {
             date_time,
-            content: content.trim().to_owned(),
+            content: trim_content(content).to_owned(),
         }
     }
 
@@ -64,8 +101,142 @@ impl LogItem {
     pub fn content(&self) -> &str {
         &self.content
     }
+
+    /// Returns true if the log item has a date but no content.
+    pub fn is_content_empty(&self) -> bool {
+        self.content.is_empty()
+    }
+
+    /// Returns the number of words of the content, which are separated by white space.
+    pub fn word_count(&self) -> usize {
+        self.content.split_whitespace().count()
+    }
+
+    /// Expands the placeholders in `template` listed in `LOG_ITEM_TEMPLATE_PLACEHOLDERS`.
+    /// Unknown placeholders are left as they are.
+    pub fn expand_template(&self, template: &str) -> String {
+        expand_template(template, |name| match name {
+            "date" => Some(self.date_time.format("%Y-%m-%d").to_string()),
+            "time" => Some(self.date_time.format("%H:%M").to_string()),
+            "datetime" => Some(self.date_time.format("%Y-%m-%d %H:%M").to_string()),
+            "weekday" => Some(self.date_time.weekday().to_string()),
+            "content" => Some(self.content.clone()),
+            _ => None,
+        })
+    }
+
+    /// Adds `prefix` before and `suffix` after the content, unless the content already
+    /// has them, so that a message written with the affixes doesn't get them twice.
+    pub fn add_affixes(&mut self, prefix: &str, suffix: &str) {
+        // The whitespace around the content is trimmed, including that of the affixes
+        let prefix = if self.content.starts_with(prefix.trim_start()) {
+            ""
+        } else {
+            prefix
+        };
+        let suffix = if self.content.ends_with(suffix.trim_end()) {
+            ""
+        } else {
+            suffix
+        };
+        let content = format!("{}{}{}", prefix, self.content, suffix);
+        self.content = trim_content(&content).to_owned();
+    }
+
+    /// Returns a short ID computed from the date time and the content, which
+    /// stays the same as long as the log item isn't edited.
+    pub fn id(&self) -> String {
+        // 64-bit FNV-1a, which unlike `DefaultHasher` is stable across Rust versions
+        let mut hash: u64 = 0xcbf29ce484222325;
+        for byte in self.to_string().bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+        format!("{:016x}", hash)[..LOG_ITEM_ID_LEN].to_owned()
+    }
+}
+
+/// The placeholders expanded by `LogItem::expand_template`.
+pub const LOG_ITEM_TEMPLATE_PLACEHOLDERS: &str = "{date}, {time}, {datetime}, {weekday}, {content}";
+
+/// Trims the whitespace around `content`, but keeps the indentation of the first
+/// non-blank line if it isn't the first line, e.g. for a pasted code block.
+fn trim_content(content: &str) -> &str {
+    let content = content.trim_end();
+    let Some(text_begin) = content.find(|c: char| !c.is_whitespace()) else {
+        return "";
+    };
+    match content[..text_begin].rfind('\n') {
+        Some(line_begin) => &content[line_begin..],
+        None => &content[text_begin..],
+    }
+}
+
+/// Escapes a content line that would be read as the header of a new log item.
+const HEADER_ESCAPE: char = '\\';
+
+/// Returns true if `line` would start a new log item once the `HEADER_ESCAPE`s at its
+/// beginning are removed.
+fn is_header_like(line: &str) -> bool {
+    LogItem::from_str(line.trim_start_matches(HEADER_ESCAPE)).is_ok()
+}
+
+/// Adds a `HEADER_ESCAPE` before every header-like line of `content` except the first,
+/// which is written after the header of the log item.
+fn escape_content(content: &str) -> String {
+    let mut lines = content.split('\n');
+    let mut escaped = lines.next().unwrap_or_default().to_owned();
+    for line in lines {
+        escaped.push('\n');
+        if is_header_like(line) {
+            escaped.push(HEADER_ESCAPE);
+        }
+        escaped.push_str(line);
+    }
+    escaped
+}
+
+/// The inverse of `escape_content`.
+fn unescape_content(content: &str) -> String {
+    let mut lines = content.split('\n');
+    let mut unescaped = lines.next().unwrap_or_default().to_owned();
+    for line in lines {
+        unescaped.push('\n');
+        match line.strip_prefix(HEADER_ESCAPE) {
+            Some(rest) if is_header_like(rest) => unescaped.push_str(rest),
+            _ => unescaped.push_str(line),
+        }
+    }
+    unescaped
+}
+
+/// The inclusive bounds of the number of words of the log items to keep,
+/// e.g. to only review the substantial ones. A missing bound doesn't limit.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WordCountRange {
+    pub min: Option<usize>,
+    pub max: Option<usize>,
+}
+
+impl WordCountRange {
+    pub fn contains(&self, log_item: &LogItem) -> bool {
+        let word_count = log_item.word_count();
+        self.min.is_none_or(|min| word_count >= min) && self.max.is_none_or(|max| word_count <= max)
+    }
 }
 
+/// The number of hexadecimal digits of `LogItem::id`.
+pub const LOG_ITEM_ID_LEN: usize = 7;
+
+/// A log file is a list of log items, each starting with a `[%Y-%m-%d %H:%M]` header
+/// at the beginning of a line, followed by its content on the same and the following
+/// lines. A content line that looks like a header is written with a leading `\`,
+/// e.g. `\[2024-01-02 12:00] not a new item`, and one more `\` is added if the line
+/// already starts with backslashes, so that it isn't split into a new log item.
+///
+/// The timestamps have a precision of minutes, so log items written in a burst share one.
+/// Their order is the order in the file, which is the order they were written in, and
+/// the sorting is stable to keep it.
 pub struct LogItemList {
     items: Vec<LogItem>,
 }
@@ -74,6 +245,11 @@ impl FromStr for LogItemList {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Files written on Windows or by some editors start with a BOM and use CRLF
+        let s = s
+            .strip_prefix('\u{feff}')
+            .unwrap_or(s)
+            .replace("\r\n", "\n");
         let mut log_items: Vec<LogItem> = Vec::new();
         let mut current_log = String::new();
 
@@ -100,6 +276,24 @@ impl FromStr for LogItemList {
     }
 }
 
+impl std::fmt::Display for LogItemList {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for item in self.iter() {
+            write!(fmt, "{}", item)?;
+        }
+        Ok(())
+    }
+}
+
+impl IntoIterator for LogItemList {
+    type Item = LogItem;
+    type IntoIter = std::vec::IntoIter<LogItem>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
 impl LogItemList {
     pub fn iter(&self) -> std::slice::Iter<'_, LogItem> {
         self.items.iter()
@@ -108,6 +302,76 @@ impl LogItemList {
     pub(crate) fn new() -> Self {
         Self { items: Vec::new() }
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn pop(&mut self) -> Option<LogItem> {
+        self.items.pop()
+    }
+
+    pub fn remove(&mut self, index: usize) -> LogItem {
+        self.items.remove(index)
+    }
+
+    /// Removes the log items equal to the one before them, and returns how many were removed.
+    pub fn dedup(&mut self) -> usize {
+        let len = self.items.len();
+        self.items.dedup();
+        len - self.items.len()
+    }
+
+    /// Keeps only the log items for which `f` returns true, and returns how many were removed.
+    pub fn retain(&mut self, f: impl FnMut(&LogItem) -> bool) -> usize {
+        let len = self.items.len();
+        self.items.retain(f);
+        len - self.items.len()
+    }
+
+    /// Removes the log items without content, and returns how many were removed.
+    pub fn remove_empty_content(&mut self) -> usize {
+        let len = self.items.len();
+        self.items.retain(|item| !item.is_content_empty());
+        len - self.items.len()
+    }
+
+    /// Sorts the log items by date time. Items with the same date time keep their order.
+    pub fn sort(&mut self) {
+        self.items.sort_by_key(|item| item.date_time);
+    }
+
+    /// Inserts the log item after all items that aren't later than it, keeping the list
+    /// in chronological order. Returns false without inserting if an equal item exists.
+    pub fn insert_sorted(&mut self, log_item: LogItem) -> bool {
+        let index = self
+            .items
+            .partition_point(|item| item.date_time <= log_item.date_time);
+        let is_duplicate = self.items[..index]
+            .iter()
+            .rev()
+            .any(|item| item.date_time == log_item.date_time && item.content == log_item.content);
+        if is_duplicate {
+            return false;
+        }
+        self.items.insert(index, log_item);
+        true
+    }
+
+    /// Reads and parses the log file at `file_path`.
+    pub fn from_log_file(file_path: &Path) -> std::io::Result<Self> {
+        let file_content = std::fs::read_to_string(file_path)?;
+        Ok(LogItemList::from_str(&file_content).expect("Invalid log file"))
+    }
+
+    /// Overwrites the file at `file_path` with the log items.
+    pub fn write_to_file(&self, file_path: &Path) -> std::io::Result<()> {
+        std::fs::write(file_path, self.to_string())
+    }
 }
 
 #[cfg(test)]
@@ -116,7 +380,7 @@ mod test {
 
     use chrono::NaiveDateTime;
 
-    use super::LogItemList;
+    use super::{LogItem, LogItemList, WordCountRange, LOG_ITEM_ID_LEN};
 
     #[test]
     fn test_log_item_list_from_str() {
@@ -134,4 +398,196 @@ mod test {
             assert_eq!(item.content(), contetns[i])
         }
     }
+
+    #[test]
+    fn test_log_item_list_pop_and_to_string() {
+        let s = "[2024-01-02 14:59] first\n[2024-01-02 15:00] second\nline\n";
+
+        let mut log_item_list = LogItemList::from_str(s).unwrap();
+        assert_eq!(log_item_list.to_string(), s);
+        assert_eq!(log_item_list.pop().unwrap().content(), "second\nline");
+        assert_eq!(log_item_list.to_string(), "[2024-01-02 14:59] first\n");
+        log_item_list.pop();
+        assert!(log_item_list.is_empty());
+        assert!(log_item_list.pop().is_none());
+    }
+
+    #[test]
+    fn test_log_item_id() {
+        let date_time =
+            NaiveDateTime::parse_from_str("2024-01-02 14:59", "%Y-%m-%d %H:%M").unwrap();
+        let id = LogItem::new(date_time, "first").id();
+        assert_eq!(id.len(), LOG_ITEM_ID_LEN);
+        assert!(id.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(id, LogItem::new(date_time, "first").id());
+        assert_ne!(id, LogItem::new(date_time, "second").id());
+    }
+
+    #[test]
+    fn test_log_item_list_insert_sorted() {
+        let s = "[2024-01-02 09:00] first\n[2024-01-02 12:00] third\n";
+        let date_time = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+
+        let mut log_item_list = LogItemList::from_str(s).unwrap();
+        assert!(log_item_list.insert_sorted(LogItem::new(date_time("2024-01-02 10:00"), "second")));
+        assert!(log_item_list.insert_sorted(LogItem::new(date_time("2024-01-02 12:00"), "fourth")));
+        assert!(!log_item_list.insert_sorted(LogItem::new(date_time("2024-01-02 09:00"), "first")));
+        assert_eq!(
+            log_item_list.to_string(),
+            "[2024-01-02 09:00] first\n\
+             [2024-01-02 10:00] second\n\
+             [2024-01-02 12:00] third\n\
+             [2024-01-02 12:00] fourth\n"
+        );
+    }
+
+    #[test]
+    fn test_burst_order_is_kept() {
+        let date_time = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+        let mut s: String = (0..100)
+            .map(|i| format!("[2024-01-02 12:00] burst {}\n", i))
+            .collect();
+        s.push_str("[2024-01-02 11:00] earlier\n");
+
+        let mut log_item_list = LogItemList::from_str(&s).unwrap();
+        log_item_list.sort();
+        for i in 100..150 {
+            let log_item = LogItem::new(date_time("2024-01-02 12:00"), &format!("burst {}", i));
+            assert!(log_item_list.insert_sorted(log_item));
+        }
+        let contents: Vec<&str> = log_item_list.iter().map(|x| x.content()).collect();
+        let expected: Vec<String> = std::iter::once("earlier".to_owned())
+            .chain((0..150).map(|i| format!("burst {}", i)))
+            .collect();
+        assert_eq!(contents, expected);
+    }
+
+    #[test]
+    fn test_log_item_keeps_indentation() {
+        let s = "[2024-01-02 14:59] fn main() {\n    println!();\n\n        // nested\n}\n\
+                 [2024-01-02 15:00]\n    indented first line\n  second line\n";
+
+        let log_item_list = LogItemList::from_str(s).unwrap();
+        let contents: Vec<&str> = log_item_list.iter().map(|x| x.content()).collect();
+        assert_eq!(
+            contents,
+            [
+                "fn main() {\n    println!();\n\n        // nested\n}",
+                "\n    indented first line\n  second line"
+            ]
+        );
+        assert_eq!(log_item_list.to_string(), s);
+
+        let date_time =
+            NaiveDateTime::parse_from_str("2024-01-02 14:59", "%Y-%m-%d %H:%M").unwrap();
+        assert_eq!(
+            LogItem::new(date_time, "  \n\n  code  \n").content(),
+            "\n  code"
+        );
+        assert_eq!(LogItem::new(date_time, " \t text \n").content(), "text");
+        assert_eq!(LogItem::new(date_time, " \n ").content(), "");
+    }
+
+    #[test]
+    fn test_word_count_range() {
+        let date_time =
+            NaiveDateTime::parse_from_str("2024-05-06 12:00", "%Y-%m-%d %H:%M").unwrap();
+        let log_item = LogItem::new(date_time, "three  short\n words");
+        assert_eq!(log_item.word_count(), 3);
+
+        let range = |min, max| WordCountRange { min, max };
+        assert!(range(None, None).contains(&log_item));
+        assert!(range(Some(3), None).contains(&log_item));
+        assert!(!range(Some(4), None).contains(&log_item));
+        assert!(range(None, Some(3)).contains(&log_item));
+        assert!(!range(None, Some(2)).contains(&log_item));
+        assert!(range(Some(3), Some(3)).contains(&log_item));
+
+        let empty_log_item = LogItem::new(date_time, "");
+        assert!(range(None, Some(0)).contains(&empty_log_item));
+        assert!(!range(Some(1), None).contains(&empty_log_item));
+    }
+
+    #[test]
+    fn test_log_item_expand_template() {
+        let date_time =
+            NaiveDateTime::parse_from_str("2024-05-01 08:30", "%Y-%m-%d %H:%M").unwrap();
+        let log_item = LogItem::new(date_time, "first\nline");
+        assert_eq!(
+            log_item.expand_template("{date} {time} | {content}"),
+            "2024-05-01 08:30 | first\nline"
+        );
+        assert_eq!(
+            log_item.expand_template("[{datetime}] {weekday} {unknown}"),
+            "[2024-05-01 08:30] Wed {unknown}"
+        );
+    }
+
+    #[test]
+    fn test_log_item_add_affixes() {
+        let mut log_item = LogItem::new(NaiveDateTime::default(), "content");
+        log_item.add_affixes("- ", " ;");
+        assert_eq!(log_item.content(), "- content ;");
+        log_item.add_affixes("- ", " ;");
+        assert_eq!(log_item.content(), "- content ;");
+        log_item.add_affixes("", "");
+        assert_eq!(log_item.content(), "- content ;");
+
+        let mut log_item = LogItem::new(NaiveDateTime::default(), "content");
+        log_item.add_affixes(" * ", "");
+        assert_eq!(log_item.content(), "* content");
+    }
+
+    #[test]
+    fn test_header_like_content_is_not_split() {
+        let date_time =
+            NaiveDateTime::parse_from_str("2024-01-02 14:59", "%Y-%m-%d %H:%M").unwrap();
+        let contents = [
+            "quoted:\n[2024-01-02 12:00] not a new item",
+            "\n[2024-01-02 12:00]",
+            "\\[2024-01-02 12:00] first line\n\\[2024-01-02 12:00] escaped\n\\\\[2024-01-02 12:00]",
+            "[not a date]\n[2024-13-02 12:00]\n \\[2024-01-02 12:00]",
+        ];
+        let mut log_item_list = LogItemList::new();
+        for content in contents {
+            log_item_list.insert_sorted(LogItem::new(date_time, content));
+        }
+
+        let s = log_item_list.to_string();
+        assert_eq!(
+            s,
+            "[2024-01-02 14:59] quoted:\n\\[2024-01-02 12:00] not a new item\n\
+             [2024-01-02 14:59]\n\\[2024-01-02 12:00]\n\
+             [2024-01-02 14:59] \\[2024-01-02 12:00] first line\n\\\\[2024-01-02 12:00] escaped\n\\\\\\[2024-01-02 12:00]\n\
+             [2024-01-02 14:59] [not a date]\n[2024-13-02 12:00]\n \\[2024-01-02 12:00]\n"
+        );
+        let parsed = LogItemList::from_str(&s).unwrap();
+        let parsed_contents: Vec<&str> = parsed.iter().map(|x| x.content()).collect();
+        assert_eq!(parsed_contents, contents);
+
+        // A header that isn't escaped still starts a new log item
+        let s = "[2024-01-02 14:59] first\n[2024-01-02 15:00] second\n\\[2024-01-02 15:01]";
+        let parsed = LogItemList::from_str(s).unwrap();
+        let parsed_contents: Vec<&str> = parsed.iter().map(|x| x.content()).collect();
+        assert_eq!(parsed_contents, ["first", "second\n[2024-01-02 15:01]"]);
+    }
+
+    #[test]
+    fn test_log_item_list_from_str_with_bom_and_crlf() {
+        let s = "\u{feff}[2024-01-02 14:59] first\r\nline\r\n[2024-01-02 15:00] second\r\n";
+        let log_item_list = LogItemList::from_str(s).unwrap();
+        let contents: Vec<&str> = log_item_list.iter().map(|x| x.content()).collect();
+        assert_eq!(contents, ["first\nline", "second"]);
+        assert_eq!(
+            log_item_list.to_string(),
+            "[2024-01-02 14:59] first\nline\n[2024-01-02 15:00] second\n"
+        );
+
+        let log_dir = tempfile::tempdir().unwrap();
+        let file_path = log_dir.path().join("log.txt");
+        std::fs::write(&file_path, "\u{feff}[2024-01-02 14:59] only\r\n").unwrap();
+        let log_item_list = LogItemList::from_log_file(&file_path).unwrap();
+        assert_eq!(log_item_list.len(), 1);
+        assert_eq!(log_item_list.iter().next().unwrap().content(), "only");
+    }
 }
@@ -4,6 +4,12 @@ use std::str::FromStr;
 pub enum Command {
     ShowDate,
     SetDate(String),
+    /// Open the log file of the date in the editor
+    Edit(String),
+    /// Highlight the matches of the pattern in a color of its own
+    Highlight(String),
+    /// Clear the highlights added by `Highlight`
+    ClearHighlights,
     None,
 }
 
@@ -14,14 +20,16 @@ impl FromStr for Command {
         if parts.is_empty() {
             return Ok(Command::None);
         }
-        Ok(if parts[0] == "date" {
-            match parts.len() {
-                1 => Command::ShowDate,
-                2 => Command::SetDate(parts[1].to_owned()),
-                _ => Command::None,
-            }
-        } else {
-            Command::None
+        // The pattern may contain spaces
+        if let Some(pattern) = s.trim().strip_prefix("hl ") {
+            return Ok(Command::Highlight(pattern.trim_start().to_owned()));
+        }
+        Ok(match (parts[0], parts.len()) {
+            ("date", 1) => Command::ShowDate,
+            ("date", 2) => Command::SetDate(parts[1].to_owned()),
+            ("e", 2) => Command::Edit(parts[1].to_owned()),
+            ("nohl", 1) => Command::ClearHighlights,
+            _ => Command::None,
         })
     }
 }
@@ -42,5 +50,16 @@ mod test {
             Ok(Command::SetDate("2021-12-5".to_owned()))
         );
         assert_eq!(Command::from_str("date"), Ok(Command::ShowDate));
+        assert_eq!(
+            Command::from_str("e 2024-05-01"),
+            Ok(Command::Edit("2024-05-01".to_owned()))
+        );
+        assert_eq!(Command::from_str("e"), Ok(Command::None));
+        assert_eq!(
+            Command::from_str(" hl  foo bar "),
+            Ok(Command::Highlight("foo bar".to_owned()))
+        );
+        assert_eq!(Command::from_str("hl"), Ok(Command::None));
+        assert_eq!(Command::from_str("nohl"), Ok(Command::ClearHighlights));
     }
 }
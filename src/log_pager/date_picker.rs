@@ -0,0 +1,177 @@
+use std::io::{stdout, Write};
+
+use chrono::NaiveDate;
+use crossterm::{
+    cursor, queue,
+    style::{Print, Stylize},
+    terminal::Clear,
+};
+
+use crate::{
+    config::DisplayConfig,
+    utils::{
+        terminal::{get_terminal_total_rows, TerminalGuard},
+        time::weekday_name,
+    },
+};
+
+use super::events::picker_event::PickerEvent;
+
+const TITLE: &str = "Pick a date (↑/↓ select, Enter open, Esc cancel)";
+
+/// A list of dates to choose one from, e.g. the dates with log files.
+pub struct DatePicker {
+    /// The dates in descending order, so that the most recent one is at the top.
+    dates: Vec<NaiveDate>,
+    selected_index: usize,
+    /// The index in `dates` of the first shown date.
+    begin_index: usize,
+    terminal_total_rows: u16,
+    display_config: DisplayConfig,
+    is_exit: bool,
+}
+
+impl DatePicker {
+    pub fn new(mut dates: Vec<NaiveDate>) -> Self {
+        dates.sort_by(|a, b| b.cmp(a));
+        Self {
+            dates,
+            selected_index: 0,
+            begin_index: 0,
+            terminal_total_rows: get_terminal_total_rows(),
+            display_config: DisplayConfig::default(),
+            is_exit: false,
+        }
+    }
+
+    pub fn set_display_config(&mut self, display_config: DisplayConfig) {
+        self.display_config = display_config;
+    }
+
+    /// The number of dates that fit below the title.
+    fn page_height(&self) -> usize {
+        (self.terminal_total_rows as usize).saturating_sub(1).max(1)
+    }
+
+    /// Scrolls the list so that the selected date is shown.
+    fn scroll_to_selected(&mut self) {
+        let page_height = self.page_height();
+        if self.selected_index < self.begin_index {
+            self.begin_index = self.selected_index;
+        } else if self.selected_index >= self.begin_index + page_height {
+            self.begin_index = self.selected_index + 1 - page_height;
+        }
+    }
+
+    fn select_next(&mut self) {
+        if self.selected_index + 1 < self.dates.len() {
+            self.selected_index += 1;
+            self.scroll_to_selected();
+        }
+    }
+
+    fn select_prev(&mut self) {
+        self.selected_index = self.selected_index.saturating_sub(1);
+        self.scroll_to_selected();
+    }
+
+    fn resize(&mut self, rows: u16) {
+        self.terminal_total_rows = rows;
+        self.scroll_to_selected();
+    }
+
+    fn print_picker(&self) -> Result<(), std::io::Error> {
+        let mut stdout = stdout();
+        queue!(
+            stdout,
+            Clear(crossterm::terminal::ClearType::All),
+            cursor::MoveTo(0, 0),
+            cursor::Hide,
+            Print(TITLE.dark_grey())
+        )?;
+        let shown_dates = self
+            .dates
+            .iter()
+            .enumerate()
+            .skip(self.begin_index)
+            .take(self.page_height());
+        for (row, (index, date)) in (1..).zip(shown_dates) {
+            let line = format!(
+                "{} {}",
+                date,
+                weekday_name(*date, self.display_config.locale)
+            );
+            queue!(stdout, cursor::MoveTo(0, row))?;
+            if index == self.selected_index {
+                queue!(stdout, Print(line.reverse()))?;
+            } else {
+                queue!(stdout, Print(line))?;
+            }
+        }
+        stdout.flush()
+    }
+
+    /// Shows the dates until one is selected, and returns it. Returns `None` if the
+    /// picking is cancelled or there is no date.
+    pub fn run(&mut self) -> Option<NaiveDate> {
+        if self.dates.is_empty() {
+            return None;
+        }
+        let _terminal_guard = TerminalGuard::new(self.display_config.alternate_screen)
+            .expect("Unable to prepare the terminal");
+        self.print_picker()
+            .expect("Unable to print the date picker");
+
+        while !self.is_exit {
+            let crossterm_event = crossterm::event::read().expect("Unable to read the event");
+            match PickerEvent::from_crossterm_event(crossterm_event) {
+                PickerEvent::Next => self.select_next(),
+                PickerEvent::Prev => self.select_prev(),
+                PickerEvent::Select => return Some(self.dates[self.selected_index]),
+                PickerEvent::Cancel => self.is_exit = true,
+                PickerEvent::Resize(_, rows) => self.resize(rows),
+                PickerEvent::None => {}
+            }
+            self.print_picker()
+                .expect("Unable to print the date picker");
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{Days, NaiveDate};
+
+    use super::DatePicker;
+
+    #[test]
+    fn test_select_and_scroll() {
+        let first_date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        let dates = (0..10).map(|day| first_date + Days::new(day)).collect();
+        let mut picker = DatePicker::new(dates);
+        picker.resize(5);
+        assert_eq!(picker.dates[0], first_date + Days::new(9));
+
+        picker.select_prev();
+        assert_eq!(picker.selected_index, 0);
+        for _ in 0..5 {
+            picker.select_next();
+        }
+        assert_eq!(picker.selected_index, 5);
+        // Four dates fit below the title
+        assert_eq!(picker.begin_index, 2);
+
+        for _ in 0..10 {
+            picker.select_next();
+        }
+        assert_eq!(picker.selected_index, 9);
+        assert_eq!(picker.dates[picker.selected_index], first_date);
+        assert_eq!(picker.begin_index, 6);
+
+        for _ in 0..8 {
+            picker.select_prev();
+        }
+        assert_eq!(picker.begin_index, 1);
+    }
+}
@@ -0,0 +1,72 @@
+use super::pager::Pager;
+
+pub trait EntryNavigation {
+    /// Moves the first line of the page to the beginning of the next log item.
+    /// Returns `false` if there is no next log item.
+    fn next_entry(&mut self) -> bool;
+    /// Moves the first line of the page to the beginning of the current log item,
+    /// or of the previous one if the page already begins with the current log item.
+    /// Returns `false` if there is no previous log item.
+    fn prev_entry(&mut self) -> bool;
+}
+
+impl<T> EntryNavigation for T
+where
+    T: Pager,
+{
+    fn next_entry(&mut self) -> bool {
+        let begin_line_index = self.begin_line_index();
+        let next_entry_line_index = entry_begin_line_indices(self.line_item_indices())
+            .find(|line_index| *line_index > begin_line_index);
+        match next_entry_line_index {
+            Some(line_index) => {
+                self.set_begin_line_index(line_index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn prev_entry(&mut self) -> bool {
+        let begin_line_index = self.begin_line_index();
+        let prev_entry_line_index = entry_begin_line_indices(self.line_item_indices())
+            .take_while(|line_index| *line_index < begin_line_index)
+            .last();
+        match prev_entry_line_index {
+            Some(line_index) => {
+                self.set_begin_line_index(line_index);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Returns the indices of the lines where a log item begins.
+fn entry_begin_line_indices(
+    line_item_indices: &[Option<usize>],
+) -> impl Iterator<Item = usize> + '_ {
+    line_item_indices
+        .iter()
+        .enumerate()
+        .filter(|(line_index, item_index)| {
+            item_index.is_some()
+                && (*line_index == 0 || line_item_indices[line_index - 1] != **item_index)
+        })
+        .map(|(line_index, _)| line_index)
+}
+
+#[cfg(test)]
+mod test {
+    use super::entry_begin_line_indices;
+
+    #[test]
+    fn test_entry_begin_line_indices() {
+        let line_item_indices = [None, Some(0), Some(0), Some(1), None, Some(2), Some(2)];
+        assert_eq!(
+            entry_begin_line_indices(&line_item_indices).collect::<Vec<_>>(),
+            [1, 3, 5]
+        );
+        assert_eq!(entry_begin_line_indices(&[]).count(), 0);
+    }
+}
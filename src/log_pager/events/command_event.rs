@@ -24,6 +24,7 @@ impl CommandEvent {
                 } else if key_event.modifiers.contains(KeyModifiers::CONTROL) {
                     match key_event.code {
                         KeyCode::Char('u') => CommandEvent::ClearLine,
+                        KeyCode::Char('c') => CommandEvent::Cancel,
                         _ => CommandEvent::None,
                     }
                 } else {
@@ -0,0 +1,37 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+
+pub enum GotoDateEvent {
+    Backspace,
+    Cancel,
+    Char(char),
+    ClearLine,
+    Confirm,
+    None,
+}
+
+impl GotoDateEvent {
+    pub fn from_crossterm_event(crossterm_event: crossterm::event::Event) -> Self {
+        match crossterm_event {
+            crossterm::event::Event::Key(key_event) => {
+                if key_event.modifiers.is_empty() {
+                    match key_event.code {
+                        KeyCode::Esc => GotoDateEvent::Cancel,
+                        KeyCode::Char(c) => GotoDateEvent::Char(c),
+                        KeyCode::Enter => GotoDateEvent::Confirm,
+                        KeyCode::Backspace => GotoDateEvent::Backspace,
+                        _ => GotoDateEvent::None,
+                    }
+                } else if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                    match key_event.code {
+                        KeyCode::Char('u') => GotoDateEvent::ClearLine,
+                        KeyCode::Char('c') => GotoDateEvent::Cancel,
+                        _ => GotoDateEvent::None,
+                    }
+                } else {
+                    GotoDateEvent::None
+                }
+            }
+            _ => GotoDateEvent::None,
+        }
+    }
+}
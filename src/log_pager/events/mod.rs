@@ -1,3 +1,5 @@
 pub mod command_event;
+pub mod goto_date_event;
+pub mod picker_event;
 pub mod search_event;
 pub mod view_event;
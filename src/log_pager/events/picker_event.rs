@@ -0,0 +1,37 @@
+use crossterm::event::{KeyCode, KeyModifiers};
+
+pub enum PickerEvent {
+    Next,
+    Prev,
+    /// Open the selected date
+    Select,
+    Cancel,
+    Resize(u16, u16),
+    None,
+}
+
+impl PickerEvent {
+    pub fn from_crossterm_event(crossterm_event: crossterm::event::Event) -> Self {
+        match crossterm_event {
+            crossterm::event::Event::Key(key_event)
+                if key_event.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                match key_event.code {
+                    KeyCode::Char('c') => PickerEvent::Cancel,
+                    KeyCode::Char('n') => PickerEvent::Next,
+                    KeyCode::Char('p') => PickerEvent::Prev,
+                    _ => PickerEvent::None,
+                }
+            }
+            crossterm::event::Event::Key(key_event) => match key_event.code {
+                KeyCode::Down | KeyCode::Char('j') => PickerEvent::Next,
+                KeyCode::Up | KeyCode::Char('k') => PickerEvent::Prev,
+                KeyCode::Enter => PickerEvent::Select,
+                KeyCode::Esc | KeyCode::Char('q') => PickerEvent::Cancel,
+                _ => PickerEvent::None,
+            },
+            crossterm::event::Event::Resize(columns, rows) => PickerEvent::Resize(columns, rows),
+            _ => PickerEvent::None,
+        }
+    }
+}
@@ -6,6 +6,10 @@ pub enum SearchEvent {
     Char(char),
     ClearLine,
     Confirm,
+    /// Recall the next search pattern in the history
+    HistoryNext,
+    /// Recall the previous search pattern in the history
+    HistoryPrev,
     None,
 }
 
@@ -19,11 +23,14 @@ impl SearchEvent {
                         KeyCode::Char(c) => SearchEvent::Char(c),
                         KeyCode::Enter => SearchEvent::Confirm,
                         KeyCode::Backspace => SearchEvent::Backspace,
+                        KeyCode::Up => SearchEvent::HistoryPrev,
+                        KeyCode::Down => SearchEvent::HistoryNext,
                         _ => SearchEvent::None,
                     }
                 } else if key_event.modifiers.contains(KeyModifiers::CONTROL) {
                     match key_event.code {
                         KeyCode::Char('u') => SearchEvent::ClearLine,
+                        KeyCode::Char('c') => SearchEvent::Cancel,
                         _ => SearchEvent::None,
                     }
                 } else {
@@ -1,38 +1,91 @@
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyModifiers};
 
 pub enum ViewEvent {
     NextDay,
     PrevDay,
     NextLine,
+    NextPage,
+    PrevPage,
+    NextHalfPage,
+    PrevHalfPage,
+    ScrollLeft,
+    ScrollRight,
+    ToggleWrap,
+    /// Switch between the date times and the relative times of the log items
+    ToggleRelativeTime,
     PrevLine,
     GotoPageBegin,
     GotoPageEnd,
     Quit,
+    /// The first `Z` of the two-key `ZZ` quit sequence
+    QuitPrefix,
     Edit,
+    /// Switch between the parsed log items and the raw file content
+    ToggleRawContent,
     SearchNext,
     SearchPrev,
+    NextEntry,
+    PrevEntry,
     Resize(u16, u16),
     EnterCommandMode,
     EnterSearchMode,
+    /// Type a date to go to
+    EnterGotoDateMode,
+    /// Open the date of the current line in a single-date pager
+    OpenDate,
+    /// Delete the log item of the top line, after a confirmation
+    Delete,
+    /// Answer yes to a confirmation
+    Confirm,
+    /// Copy the content of the log item of the top line to the clipboard,
+    /// or answer yes to a confirmation
+    Copy,
     None,
 }
 
 impl ViewEvent {
     pub fn from_crossterm_event(crossterm_event: crossterm::event::Event) -> Self {
         match crossterm_event {
+            crossterm::event::Event::Key(key_event)
+                if key_event.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                match key_event.code {
+                    KeyCode::Char('c') => ViewEvent::Quit,
+                    KeyCode::Char('f') => ViewEvent::NextPage,
+                    KeyCode::Char('b') => ViewEvent::PrevPage,
+                    KeyCode::Char('d') => ViewEvent::NextHalfPage,
+                    KeyCode::Char('u') => ViewEvent::PrevHalfPage,
+                    _ => ViewEvent::None,
+                }
+            }
             crossterm::event::Event::Key(key_event) => match key_event.code {
                 KeyCode::Char('j') => ViewEvent::NextLine,
                 KeyCode::Char('k') => ViewEvent::PrevLine,
-                KeyCode::Char('g') => ViewEvent::GotoPageBegin,
-                KeyCode::Char('G') => ViewEvent::GotoPageEnd,
+                KeyCode::PageDown | KeyCode::Char(' ') | KeyCode::Char('f') => ViewEvent::NextPage,
+                KeyCode::PageUp | KeyCode::Char('b') => ViewEvent::PrevPage,
+                KeyCode::Left => ViewEvent::ScrollLeft,
+                KeyCode::Right => ViewEvent::ScrollRight,
+                KeyCode::Char('w') => ViewEvent::ToggleWrap,
+                KeyCode::Char('t') => ViewEvent::ToggleRelativeTime,
+                KeyCode::Home | KeyCode::Char('g') => ViewEvent::GotoPageBegin,
+                KeyCode::End | KeyCode::Char('G') => ViewEvent::GotoPageEnd,
                 KeyCode::Char('l') => ViewEvent::NextDay,
                 KeyCode::Char('h') => ViewEvent::PrevDay,
-                KeyCode::Char('q') => ViewEvent::Quit,
+                KeyCode::Char('q') | KeyCode::Char('Q') => ViewEvent::Quit,
+                KeyCode::Char('Z') => ViewEvent::QuitPrefix,
                 KeyCode::Char('e') => ViewEvent::Edit,
+                KeyCode::Char('R') => ViewEvent::ToggleRawContent,
                 KeyCode::Char('n') => ViewEvent::SearchNext,
                 KeyCode::Char('N') => ViewEvent::SearchPrev,
+                KeyCode::Char('}') => ViewEvent::NextEntry,
+                KeyCode::Char('{') => ViewEvent::PrevEntry,
                 KeyCode::Char(':') => ViewEvent::EnterCommandMode,
                 KeyCode::Char('/') => ViewEvent::EnterSearchMode,
+                KeyCode::Char('D') => ViewEvent::EnterGotoDateMode,
+                KeyCode::Enter => ViewEvent::OpenDate,
+                KeyCode::Char('d') => ViewEvent::Delete,
+                KeyCode::Char('y') => ViewEvent::Copy,
+                KeyCode::Char('Y') => ViewEvent::Confirm,
                 _ => ViewEvent::None,
             },
             crossterm::event::Event::Resize(columns, rows) => ViewEvent::Resize(columns, rows),
@@ -0,0 +1,87 @@
+use crossterm::style::{Color, Stylize};
+use regex::Regex;
+
+/// The colors of the patterns highlighted by the `:hl` command, used in turn.
+pub const HIGHLIGHT_COLORS: [Color; 5] = [
+    Color::Yellow,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Green,
+    Color::Blue,
+];
+
+/// Colors the matched text in bold. Unlike a search result, it has no background, so that
+/// it isn't found by `Search`. Each line is colored separately, like a search result.
+fn color_highlight(matched_text: &str, color: Color) -> String {
+    matched_text
+        .split('\n')
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                line.with(color).bold().to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Colors the non-empty matches of each of the `highlights` in `s` with its color, and passes
+/// the text between them to `mark_rest`. A match overlapping an earlier one is skipped, and
+/// of the matches starting at the same position, the one of the first highlight is kept.
+pub fn mark_highlights(
+    highlights: &[(Regex, Color)],
+    s: &str,
+    mark_rest: impl Fn(&str) -> String,
+) -> String {
+    let mut matches: Vec<_> = highlights
+        .iter()
+        .enumerate()
+        .flat_map(|(index, (regex, color))| {
+            regex
+                .find_iter(s)
+                .filter(|m| !m.is_empty())
+                .map(move |m| (m.start(), index, m.end(), *color))
+        })
+        .collect();
+    matches.sort_by_key(|(start, index, _, _)| (*start, *index));
+
+    let mut ret = String::new();
+    let mut last_match_end = 0;
+    for (start, _, end, color) in matches {
+        if start < last_match_end {
+            continue;
+        }
+        ret += &mark_rest(&s[last_match_end..start]);
+        ret += &color_highlight(&s[start..end], color);
+        last_match_end = end;
+    }
+    ret += &mark_rest(&s[last_match_end..]);
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use crossterm::style::{Color, Stylize};
+    use regex::Regex;
+
+    use super::mark_highlights;
+
+    #[test]
+    fn test_mark_highlights() {
+        let highlights = [
+            (Regex::new("foo|x*").unwrap(), Color::Yellow),
+            (Regex::new("o+ba|bar").unwrap(), Color::Cyan),
+        ];
+        let upper = |s: &str| s.to_uppercase();
+        assert_eq!(mark_highlights(&[], "foo", upper), "FOO");
+        assert_eq!(
+            mark_highlights(&highlights, "a foobar bar", upper),
+            format!("A {}BAR {}", "foo".yellow().bold(), "bar".cyan().bold())
+        );
+        assert_eq!(
+            mark_highlights(&highlights, "zoobar", upper),
+            format!("Z{}R", "ooba".cyan().bold())
+        );
+    }
+}
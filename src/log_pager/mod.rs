@@ -1,9 +1,15 @@
 pub mod command;
+pub mod date_picker;
+pub mod entry_navigation;
 pub mod events;
+mod highlight;
+pub mod page_scroll;
 pub mod pager;
 pub mod pager_mode;
 pub mod paging_all_pager;
 pub mod range;
-mod search;
+pub mod search;
+pub mod search_history;
 pub mod single_date_pager;
+mod syntax;
 pub mod utils;
@@ -0,0 +1,48 @@
+/// Returns the number of lines to scroll by a page, or by half a page if `half` is true.
+/// `overlap` lines of the current page stay on the screen, but the page always moves
+/// by at least one line.
+pub fn scroll_distance(page_height: usize, overlap: usize, half: bool) -> usize {
+    let distance = if half { page_height / 2 } else { page_height };
+    distance.saturating_sub(overlap).max(1)
+}
+
+/// Returns the first line of the page after scrolling by `distance` lines, which
+/// doesn't go past the last page.
+pub fn scroll_target(
+    begin_line_index: usize,
+    distance: usize,
+    forward: bool,
+    total_lines: usize,
+    page_height: usize,
+) -> usize {
+    if forward {
+        let last_page_begin = total_lines.saturating_sub(page_height);
+        (begin_line_index + distance).min(last_page_begin.max(begin_line_index))
+    } else {
+        begin_line_index.saturating_sub(distance)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{scroll_distance, scroll_target};
+
+    #[test]
+    fn test_scroll_distance() {
+        assert_eq!(scroll_distance(20, 0, false), 20);
+        assert_eq!(scroll_distance(20, 2, false), 18);
+        assert_eq!(scroll_distance(20, 2, true), 8);
+        // The overlap never exceeds the page
+        assert_eq!(scroll_distance(20, 100, false), 1);
+        assert_eq!(scroll_distance(1, 0, true), 1);
+    }
+
+    #[test]
+    fn test_scroll_target() {
+        assert_eq!(scroll_target(0, 18, true, 100, 20), 18);
+        assert_eq!(scroll_target(70, 18, true, 100, 20), 80);
+        assert_eq!(scroll_target(0, 18, true, 10, 20), 0);
+        assert_eq!(scroll_target(30, 18, false, 100, 20), 12);
+        assert_eq!(scroll_target(10, 18, false, 100, 20), 0);
+    }
+}
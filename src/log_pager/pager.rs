@@ -1,5 +1,11 @@
+use crossterm::style::Color;
+
 pub trait Pager {
     fn begin_line_index(&self) -> usize;
     fn colored_lines(&self) -> &Vec<String>;
+    /// The index of the log item that each line of `colored_lines` belongs to.
+    fn line_item_indices(&self) -> &Vec<Option<usize>>;
     fn set_begin_line_index(&mut self, line_index: usize);
+    /// The background of the search results in `colored_lines`, from `theme.search_match`.
+    fn search_background(&self) -> Color;
 }
@@ -3,4 +3,6 @@ pub enum PagerMode {
     View,
     Command,
     Search,
+    /// Typing a date to go to
+    GotoDate,
 }
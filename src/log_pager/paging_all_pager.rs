@@ -1,6 +1,7 @@
 use std::{
     borrow::Cow,
     cmp::min,
+    collections::VecDeque,
     io::{stdout, Stdout, Write},
     path::PathBuf,
     str::FromStr,
@@ -8,39 +9,100 @@ use std::{
 
 use chrono::NaiveDate;
 use crossterm::{
-    cursor, execute, queue,
-    style::{Print, Stylize},
-    terminal::{disable_raw_mode, enable_raw_mode, Clear},
+    cursor, queue,
+    style::{Color, Print, Stylize},
+    terminal::Clear,
 };
 
 use crate::{
-    config::{construct_log_file_path, get_date_from_log_file_name},
+    config::{construct_log_file_path, get_log_dates, DisplayConfig, LogConfig},
+    constants::PKG_NAME,
     log_item::{LogItem, LogItemList},
-    log_pager::search::mark_search_result,
+    log_pager::{
+        search::{
+            log_item_matches, mark_search_result, parse_search_input, SearchField,
+            DATE_SEARCH_PREFIX,
+        },
+        syntax::mark_search_result_and_syntax,
+    },
+    theme::Theme,
+    utils::{
+        fs::read_to_string_lossy,
+        terminal::TerminalGuard,
+        time::{parse_date_from_str, weekday_name},
+    },
 };
 
 use super::{
-    events::{search_event::SearchEvent, view_event::ViewEvent},
+    entry_navigation::EntryNavigation,
+    events::{goto_date_event::GotoDateEvent, search_event::SearchEvent, view_event::ViewEvent},
+    page_scroll::{scroll_distance, scroll_target},
     pager::Pager,
     pager_mode::PagerMode,
     range::Range,
     search::Search,
-    utils::{get_char_index_by_line_index, get_line_index_by_char_index},
+    search_history::SearchHistory,
+    single_date_pager::SingleDatePager,
+    utils::{
+        get_char_index_by_line_index, get_line_index_by_char_index, queue_clock,
+        queue_goto_date_input, read_event, relative_time_text, set_line_background,
+        slice_colored_line, wrap_line, CURRENT_LINE_BACKGROUND,
+    },
 };
 
+/// The number of pages kept loaded above and below the current page.
+const LOAD_BUFFER_PAGES: usize = 2;
+
+/// A pager for the logs of all days.
+///
+/// Only the days around the current page are loaded and wrapped into lines.
+/// Adjacent days are loaded as the page moves, and the days far away from
+/// the page are unloaded.
 pub struct PagingAllPager {
     log_dir_path: PathBuf,
     mode: PagerMode,
-    /// The index of the first character of the current page in the log file.
+    /// The index of the first character of the current page in the loaded lines.
     /// White space characters are ignored when calculating the index.
     begin_char_index: usize,
-    log_item_list: LogItemList,
+    /// The number of display columns the page is scrolled to the right.
+    col_offset: usize,
+    /// Whether the lines longer than the page width are wrapped.
+    /// Initialized by `display.wrap`, and toggled in the view mode.
+    wrap: bool,
+    /// The dates of all log files in ascending order, without today's if `exclude_today`
+    /// is true. Only the most recent `day_limit` ones if it is set.
+    all_dates: Vec<NaiveDate>,
+    day_limit: Option<usize>,
+    exclude_today: bool,
+    /// The index in `all_dates` of the first loaded day.
+    loaded_days_begin: usize,
+    /// The log items of the loaded days, in the same order as `all_dates`.
+    loaded_days: VecDeque<LogItemList>,
+    /// The number of lines of each loaded day.
+    day_line_counts: Vec<usize>,
     terminal_total_rows: u16,
     terminal_total_cols: u16,
     colored_lines: Vec<String>,
+    line_item_indices: Vec<Option<usize>>,
+    /// The date of the log item of each line in `colored_lines`.
+    line_dates: Vec<NaiveDate>,
     is_exit: bool,
+    /// Whether the last key was the first `Z` of `ZZ`.
+    is_quit_pending: bool,
+    /// Shown at the bottom in the view mode until the next view event.
+    status_message: String,
     search_pattern: Option<regex::Regex>,
+    search_field: SearchField,
     search_pattern_input: String,
+    search_history: SearchHistory,
+    /// The date typed in the goto-date mode.
+    goto_date_input: String,
+    display_config: DisplayConfig,
+    theme: Theme,
+    prompt_format: String,
+    date_input_formats: Vec<String>,
+    yearless_dates_in_past: bool,
+    editor: String,
 }
 
 impl PagingAllPager {
@@ -52,13 +114,33 @@ impl PagingAllPager {
             log_dir_path,
             mode: PagerMode::View,
             begin_char_index: 0,
-            log_item_list: LogItemList::new(),
+            col_offset: 0,
+            wrap: true,
+            all_dates: Vec::new(),
+            day_limit: None,
+            exclude_today: false,
+            loaded_days_begin: 0,
+            loaded_days: VecDeque::new(),
+            day_line_counts: Vec::new(),
             terminal_total_rows,
             terminal_total_cols,
             colored_lines: Vec::new(),
+            line_item_indices: Vec::new(),
+            line_dates: Vec::new(),
             is_exit: false,
+            is_quit_pending: false,
+            status_message: String::new(),
             search_pattern: None,
+            search_field: SearchField::default(),
             search_pattern_input: String::new(),
+            search_history: SearchHistory::default(),
+            goto_date_input: String::new(),
+            display_config: DisplayConfig::default(),
+            theme: Theme::default(),
+            prompt_format: LogConfig::default().prompt_format,
+            date_input_formats: LogConfig::default().date_input_formats,
+            yearless_dates_in_past: LogConfig::default().yearless_dates_in_past,
+            editor: String::new(),
         };
 
         ret.update_log_items();
@@ -67,101 +149,525 @@ impl PagingAllPager {
         ret
     }
 
+    /// Sets the prompt format of the single-date pager opened from this pager.
+    pub fn set_prompt_format(&mut self, prompt_format: String) {
+        self.prompt_format = prompt_format;
+    }
+
+    /// Sets the date formats of the single-date pager opened from this pager.
+    pub fn set_date_input_formats(&mut self, date_input_formats: Vec<String>) {
+        self.date_input_formats = date_input_formats;
+    }
+
+    /// Sets how the single-date pager opened from this pager completes a date without a year.
+    pub fn set_yearless_dates_in_past(&mut self, yearless_dates_in_past: bool) {
+        self.yearless_dates_in_past = yearless_dates_in_past;
+    }
+
+    /// Sets the editor of the single-date pager opened from this pager.
+    pub fn set_editor(&mut self, editor: String) {
+        self.editor = editor;
+    }
+
+    pub fn set_display_config(&mut self, display_config: DisplayConfig) {
+        self.wrap = display_config.wrap;
+        let is_sort_changed = self.display_config.sort_entries != display_config.sort_entries;
+        self.display_config = display_config;
+        if is_sort_changed {
+            // The loaded days are read again to be sorted
+            self.update_log_items();
+        } else {
+            self.update_colored_lines();
+        }
+    }
+
+    /// Shows only the most recent `day_limit` days with a log file, older ones are not shown.
+    pub fn set_day_limit(&mut self, day_limit: Option<usize>) {
+        self.day_limit = day_limit;
+        self.update_log_items();
+    }
+
+    /// Leaves out today's log file, e.g. to review only the completed days.
+    pub fn set_exclude_today(&mut self, exclude_today: bool) {
+        self.exclude_today = exclude_today;
+        self.update_log_items();
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        self.update_colored_lines();
+    }
+
+    /// The largest `col_offset`, at which the end of the longest line is at the right edge.
+    fn max_col_offset(&self) -> usize {
+        let max_line_width = self
+            .colored_lines
+            .iter()
+            .map(|line| textwrap::core::display_width(line))
+            .max()
+            .unwrap_or(0);
+        max_line_width.saturating_sub(self.terminal_total_cols as usize)
+    }
+
+    /// The number of columns scrolled by a horizontal scroll, which is half the page width.
+    fn horizontal_scroll_step(&self) -> usize {
+        (self.terminal_total_cols as usize / 2).max(1)
+    }
+
+    fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+        self.update_colored_lines();
+        self.update_loaded_days();
+    }
+
+    fn toggle_wrap(&mut self) {
+        // The page stays at the same text, since `begin_char_index` ignores the line breaks
+        self.set_wrap(!self.wrap);
+        self.col_offset = 0;
+    }
+
+    /// Switches between the date times and the relative times of the log items.
+    /// The page stays at the log item of the top line, since the length of the lines changes.
+    fn toggle_relative_time(&mut self) {
+        let top_item_index = self
+            .line_item_indices
+            .get(self.page_range().begin)
+            .copied()
+            .flatten();
+        self.display_config.relative_time = !self.display_config.relative_time;
+        self.update_colored_lines();
+        if let Some(line_index) = top_item_index.and_then(|item_index| {
+            self.line_item_indices
+                .iter()
+                .position(|x| *x == Some(item_index))
+        }) {
+            self.set_begin_line_index(line_index);
+        }
+        self.update_loaded_days();
+    }
+
+    fn scroll_left(&mut self) {
+        self.col_offset = self
+            .col_offset
+            .saturating_sub(self.horizontal_scroll_step());
+    }
+
+    fn scroll_right(&mut self) {
+        self.col_offset = min(
+            self.col_offset + self.horizontal_scroll_step(),
+            self.max_col_offset(),
+        );
+    }
+
     fn resize(&mut self, columns: u16, rows: u16) {
         self.terminal_total_cols = columns;
         self.terminal_total_rows = rows;
         self.update_colored_lines();
+        self.col_offset = min(self.col_offset, self.max_col_offset());
+        self.update_loaded_days();
     }
 
     fn goto_page_begin(&mut self) {
-        self.set_begin_line_index(0);
+        if self.all_dates.is_empty() {
+            return;
+        }
+        self.load_only_day(0);
+        self.update_loaded_days();
     }
 
     fn goto_page_end(&mut self) {
+        if self.all_dates.is_empty() {
+            return;
+        }
+        self.load_only_day(self.all_dates.len() - 1);
+        // Load the days before the last one, so that the last page is full.
+        while self.loaded_days_begin > 0 && self.total_content_lines() < self.page_height() {
+            self.load_prev_day();
+        }
         let original_page_range = self.page_range();
         let diff = self.total_content_lines() - original_page_range.end;
         self.set_begin_line_index(original_page_range.begin + diff);
+        self.update_loaded_days();
     }
 
-    fn highlight_log_item<'h>(&self, log_item: &'h LogItem) -> String {
-        let date_str = format!("[{}]", log_item.date_time().format("%Y-%m-%d %H:%M"));
+    /// Colors the log item. If `show_timestamp` is false, the timestamp is replaced with
+    /// spaces, unless the dates are searched.
+    fn highlight_log_item<'h>(&self, log_item: &'h LogItem, show_timestamp: bool) -> String {
+        let date_text = log_item.date_time().format("%Y-%m-%d %H:%M").to_string();
+        // The dates are searched in the date time format, so it's kept while searching them
+        let timestamp_text = if self.display_config.relative_time {
+            relative_time_text(*log_item.date_time())
+        } else {
+            date_text.clone()
+        };
+        let date_str = match &self.search_pattern {
+            Some(regex) if self.search_field == SearchField::Date => {
+                format!(
+                    "[{}]",
+                    mark_search_result(regex, &date_text, self.theme.search_match)
+                )
+            }
+            _ if !show_timestamp => " ".repeat(timestamp_text.len() + 2),
+            _ => format!("[{}]", timestamp_text)
+                .with(self.theme.timestamp)
+                .to_string(),
+        };
+        // The content is only marked if it's searched
+        let content_search_pattern = self
+            .search_pattern
+            .as_ref()
+            .filter(|_| self.search_field == SearchField::Content);
         let content: &str = log_item.content();
         let new_content: Cow<'h, str>;
-        if let Some(regex) = &self.search_pattern {
-            new_content = mark_search_result(regex, content);
+        if self.display_config.highlight_syntax {
+            new_content = Cow::Owned(mark_search_result_and_syntax(
+                content_search_pattern,
+                content,
+                self.theme.search_match,
+            ));
+        } else if let Some(regex) = content_search_pattern {
+            new_content = mark_search_result(regex, content, self.theme.search_match);
         } else {
             new_content = Cow::Borrowed(content);
         }
-        format!("{} {}", date_str.green(), new_content)
+        if self.display_config.show_entry_ids {
+            format!("{} {} {}", date_str, log_item.id().dark_grey(), new_content)
+        } else {
+            format!("{} {}", date_str, new_content)
+        }
     }
 
-    /// Splits the log content into lines that fit within the terminal width,
+    /// Splits the loaded log items into lines that fit within the terminal width,
     /// while preserving any color formatting.
     ///
     /// - For each log item, it converts the log content into a colored string.
     /// - Each line is split into smaller lines if it exceeds the terminal's width.
+    /// - A dim separator with the date and the number of log items is inserted
+    ///   before the first log item of each day.
     fn update_colored_lines(&mut self) {
         // Get the terminal's total column width.
         let terminal_total_cols = self.terminal_total_cols as usize;
 
         self.colored_lines.clear();
-        for item in self.log_item_list.iter() {
-            for line in self.highlight_log_item(item).lines() {
-                self.colored_lines.extend(
-                    textwrap::wrap(line, terminal_total_cols)
+        self.line_item_indices.clear();
+        self.line_dates.clear();
+        self.day_line_counts.clear();
+        let mut prev_date: Option<NaiveDate> = None;
+        let mut prev_date_time = None;
+        let mut item_index: usize = 0;
+        let is_hidden =
+            |item: &LogItem| self.display_config.hide_empty_entries && item.is_content_empty();
+        for log_item_list in &self.loaded_days {
+            let day_begin_line_index = self.colored_lines.len();
+            for item in log_item_list.iter() {
+                if is_hidden(item) {
+                    item_index += 1;
+                    continue;
+                }
+                let date = item.date_time().date();
+                if prev_date != Some(date) {
+                    let entries = log_item_list
                         .iter()
-                        .map(|x| x.to_string()),
-                );
+                        .filter(|x| x.date_time().date() == date && !is_hidden(x))
+                        .count();
+                    let date_line = day_separator(
+                        date,
+                        &weekday_name(date, self.display_config.locale),
+                        entries,
+                    );
+                    self.colored_lines
+                        .push(date_line.with(self.theme.date_line).to_string());
+                    self.line_item_indices.push(None);
+                    self.line_dates.push(date);
+                    prev_date = Some(date);
+                }
+                let show_timestamp = !self.display_config.group_same_minute
+                    || prev_date_time != Some(item.date_time());
+                prev_date_time = Some(item.date_time());
+                for line in self.highlight_log_item(item, show_timestamp).lines() {
+                    self.colored_lines
+                        .extend(wrap_line(line, terminal_total_cols, self.wrap));
+                }
+                self.line_item_indices
+                    .resize(self.colored_lines.len(), Some(item_index));
+                self.line_dates.resize(self.colored_lines.len(), date);
+                item_index += 1;
             }
+            self.day_line_counts
+                .push(self.colored_lines.len() - day_begin_line_index);
+        }
+    }
+
+    fn read_day(&self, date_index: usize) -> std::io::Result<LogItemList> {
+        let file_path = construct_log_file_path(&self.log_dir_path, &self.all_dates[date_index]);
+        // Show what can be read of a file that isn't valid UTF-8 rather than an empty day
+        let (file_content, _) = read_to_string_lossy(&file_path)?;
+        let mut log_item_list = LogItemList::from_str(&file_content).expect("Invalid log file");
+        if self.display_config.sort_entries {
+            log_item_list.sort();
         }
+        Ok(log_item_list)
     }
 
-    fn all_date(&self) -> std::io::Result<Vec<NaiveDate>> {
-        let mut ret = Vec::new();
-        let log_dir_path = &self.log_dir_path;
-        for entry in std::fs::read_dir(log_dir_path)? {
-            let entry = entry?;
-            let file_name = entry.file_name();
-            if let Some(date) = get_date_from_log_file_name(file_name.to_str().unwrap()) {
-                ret.push(date);
+    /// Reads the day at `date_index` to load it. A log file that can't be read is shown as
+    /// an empty day, with a status message if the permission is denied.
+    fn read_day_to_load(&mut self, date_index: usize) -> LogItemList {
+        self.read_day(date_index).unwrap_or_else(|error| {
+            if error.kind() == std::io::ErrorKind::PermissionDenied {
+                let file_path =
+                    construct_log_file_path(&self.log_dir_path, &self.all_dates[date_index]);
+                self.status_message = format!("Permission denied: '{}'", file_path.display());
             }
+            LogItemList::new()
+        })
+    }
+
+    /// The index in `all_dates` after the last loaded day.
+    fn loaded_days_end(&self) -> usize {
+        self.loaded_days_begin + self.loaded_days.len()
+    }
+
+    /// Unloads all days, loads the day at `date_index` in `all_dates`,
+    /// and moves the page to its beginning.
+    fn load_only_day(&mut self, date_index: usize) {
+        self.loaded_days.clear();
+        self.loaded_days_begin = date_index;
+        let log_item_list = self.read_day_to_load(date_index);
+        self.loaded_days.push_back(log_item_list);
+        self.update_colored_lines();
+        self.begin_char_index = 0;
+    }
+
+    fn load_next_day(&mut self) {
+        let log_item_list = self.read_day_to_load(self.loaded_days_end());
+        self.loaded_days.push_back(log_item_list);
+        self.update_colored_lines();
+    }
+
+    fn load_prev_day(&mut self) {
+        let begin_line_index = self.page_range().begin;
+        let original_total_lines = self.total_content_lines();
+        self.loaded_days_begin -= 1;
+        let log_item_list = self.read_day_to_load(self.loaded_days_begin);
+        self.loaded_days.push_front(log_item_list);
+        self.update_colored_lines();
+        // Keep the page on the same line.
+        let added_lines = self.total_content_lines() - original_total_lines;
+        self.set_begin_line_index(begin_line_index + added_lines);
+    }
+
+    fn unload_first_day(&mut self) {
+        let begin_line_index = self.page_range().begin;
+        let original_total_lines = self.total_content_lines();
+        self.loaded_days.pop_front();
+        self.loaded_days_begin += 1;
+        self.update_colored_lines();
+        // Keep the page on the same line.
+        let removed_lines = original_total_lines - self.total_content_lines();
+        self.set_begin_line_index(begin_line_index - removed_lines);
+    }
+
+    fn unload_last_day(&mut self) {
+        self.loaded_days.pop_back();
+        self.update_colored_lines();
+    }
+
+    /// Loads the days needed to fill the current page and the buffer around it,
+    /// and unloads the days that are far away from the current page.
+    fn update_loaded_days(&mut self) {
+        let buffer_lines = self.page_height() * LOAD_BUFFER_PAGES;
+
+        while self.loaded_days_end() < self.all_dates.len()
+            && self.page_range().end + buffer_lines > self.total_content_lines()
+        {
+            self.load_next_day();
+        }
+        while self.loaded_days_begin > 0 && self.page_range().begin < buffer_lines {
+            self.load_prev_day();
+        }
+
+        // Unload a day only if it's twice the buffer away, so that scrolling
+        // back and forth doesn't load and unload the same day repeatedly.
+        while self.loaded_days.len() > 1
+            && self.day_line_counts[0] + buffer_lines * 2 <= self.page_range().begin
+        {
+            self.unload_first_day();
+        }
+        while self.loaded_days.len() > 1
+            && self.page_range().end + buffer_lines * 2
+                <= self.total_content_lines() - self.day_line_counts.last().unwrap()
+        {
+            self.unload_last_day();
+        }
+    }
+
+    fn day_contains_search_result(&self, date_index: usize, regex: &regex::Regex) -> bool {
+        self.read_day(date_index).is_ok_and(|log_item_list| {
+            log_item_list
+                .iter()
+                .any(|item| log_item_matches(self.search_field, regex, item))
+        })
+    }
+
+    /// Moves the page to the next search result, looking into the days
+    /// that aren't loaded if there is none in the loaded days.
+    fn search_next_in_all_days(&mut self, skip_current_line: bool) {
+        if !self.search_next(skip_current_line) {
+            let Some(regex) = self.search_pattern.clone() else {
+                return;
+            };
+            let Some(date_index) = (self.loaded_days_end()..self.all_dates.len())
+                .find(|&date_index| self.day_contains_search_result(date_index, &regex))
+            else {
+                return;
+            };
+            self.load_only_day(date_index);
+            self.search_next(false);
         }
+        self.update_loaded_days();
+    }
 
-        Ok(ret)
+    /// Moves the page to the previous search result, looking into the days
+    /// that aren't loaded if there is none in the loaded days.
+    fn search_prev_in_all_days(&mut self) {
+        if !self.search_prev() {
+            let Some(regex) = self.search_pattern.clone() else {
+                return;
+            };
+            let Some(date_index) = (0..self.loaded_days_begin)
+                .rev()
+                .find(|&date_index| self.day_contains_search_result(date_index, &regex))
+            else {
+                return;
+            };
+            self.load_only_day(date_index);
+            // Go to the last search result of the day.
+            self.set_begin_line_index(self.total_content_lines() - 1);
+            if !self.search_next(false) {
+                self.search_prev();
+            }
+        }
+        self.update_loaded_days();
+    }
+
+    /// Moves the page to the first line of `date`, or of the day after it if there is
+    /// no log file of `date`.
+    fn goto_date(&mut self, date: NaiveDate) {
+        if self.all_dates.is_empty() {
+            return;
+        }
+        let date_index = self
+            .all_dates
+            .partition_point(|x| *x < date)
+            .min(self.all_dates.len() - 1);
+        self.load_only_day(date_index);
+        self.update_loaded_days();
+    }
+
+    /// Opens the date of the log item at the top of the page in a single-date pager,
+    /// and comes back to that date when it quits.
+    fn open_current_date(&mut self) {
+        let Some(&date) = self.line_dates.get(self.page_range().begin) else {
+            return;
+        };
+        let mut single_date_pager = SingleDatePager::new(date, self.log_dir_path.clone());
+        single_date_pager.set_display_config(self.display_config.clone());
+        single_date_pager.set_theme(self.theme);
+        single_date_pager.set_prompt_format(self.prompt_format.clone());
+        single_date_pager.set_date_input_formats(self.date_input_formats.clone());
+        single_date_pager.set_yearless_dates_in_past(self.yearless_dates_in_past);
+        single_date_pager.set_editor(self.editor.clone());
+        single_date_pager.run();
+
+        // The single-date pager restores the terminal when it quits, so prepare it again
+        crossterm::terminal::enable_raw_mode().expect("Unable to enable the raw mode");
+        if self.display_config.alternate_screen {
+            crossterm::execute!(stdout(), crossterm::terminal::EnterAlternateScreen)
+                .expect("Unable to enter alternate screen");
+        }
+
+        // The logs may have been edited in the single-date pager
+        self.read_all_dates();
+        self.goto_date(date);
+    }
+
+    /// Applies the search input, e.g. given on the command line, and goes to the first match.
+    /// If the pattern is invalid, the search is cleared and a status message is shown.
+    pub fn set_search(&mut self, search_input: &str) {
+        let (search_field, pattern) = parse_search_input(search_input);
+        self.search_field = search_field;
+        self.search_pattern = match regex::Regex::new(pattern) {
+            Ok(regex) => Some(regex),
+            Err(_) => {
+                self.status_message = format!("invalid pattern: {}", pattern);
+                None
+            }
+        };
+        self.search_history.push(search_input);
+        self.update_colored_lines();
+        self.search_next_in_all_days(false);
     }
 
     fn confirm_search(&mut self) {
         let search_pattern_input = &mut self.search_pattern_input;
-        self.search_pattern = match regex::Regex::new(search_pattern_input) {
-            Ok(result) => Some(result),
-            Err(_) => None,
-        };
+        let (search_field, pattern) = parse_search_input(search_pattern_input);
+        self.search_field = search_field;
+        self.search_pattern = regex::Regex::new(pattern).ok();
+        self.search_history.push(search_pattern_input);
         search_pattern_input.clear();
         self.update_colored_lines();
         self.enter_view_mode();
-        self.search_next(false);
-    }
-
-    fn content(&self) -> String {
-        let mut ret = String::new();
-        let mut all_date = self.all_date().unwrap();
-        all_date.sort();
-        for date in all_date {
-            let file_path = construct_log_file_path(&self.log_dir_path, &date);
-            let file_content: String = std::fs::read_to_string(&file_path).unwrap_or_default();
-            ret += &file_content;
-        }
-        ret
+        self.search_next_in_all_days(false);
     }
 
     fn enter_search_mode(&mut self) {
+        self.search_history.reset();
         self.mode = PagerMode::Search;
     }
 
+    fn enter_goto_date_mode(&mut self) {
+        self.mode = PagerMode::GotoDate;
+    }
+
     fn enter_view_mode(&mut self) {
+        self.goto_date_input.clear();
         self.mode = PagerMode::View;
     }
 
+    /// Goes to the date typed in the goto-date mode, or shows an error if it's invalid.
+    fn confirm_goto_date(&mut self) {
+        let date_str = self.goto_date_input.trim().to_owned();
+        self.enter_view_mode();
+        if date_str.is_empty() {
+            return;
+        }
+        match parse_date_from_str(
+            &date_str,
+            &self.date_input_formats,
+            self.yearless_dates_in_past,
+        ) {
+            Ok(date) => self.goto_date(date),
+            Err(_) => self.status_message = format!("invalid date: {}", date_str),
+        }
+    }
+
+    fn handle_goto_date_event(&mut self, event: GotoDateEvent) {
+        self.status_message.clear();
+        match event {
+            GotoDateEvent::Confirm => self.confirm_goto_date(),
+            GotoDateEvent::Char(c) => self.goto_date_input.push(c),
+            GotoDateEvent::None => {}
+            GotoDateEvent::Cancel => self.enter_view_mode(),
+            GotoDateEvent::Backspace => {
+                if self.goto_date_input.pop().is_none() {
+                    self.enter_view_mode();
+                }
+            }
+            GotoDateEvent::ClearLine => self.goto_date_input.clear(),
+        }
+        self.print_pager().expect("Unable to print the pager");
+    }
+
     fn exit(&mut self) {
         self.is_exit = true;
     }
@@ -180,21 +686,58 @@ impl PagingAllPager {
                 }
             }
             SearchEvent::ClearLine => self.search_pattern_input.clear(),
+            SearchEvent::HistoryPrev => {
+                if let Some(pattern) = self.search_history.recall_prev(&self.search_pattern_input) {
+                    self.search_pattern_input = pattern.to_owned();
+                }
+            }
+            SearchEvent::HistoryNext => {
+                if let Some(pattern) = self.search_history.recall_next() {
+                    self.search_pattern_input = pattern.to_owned();
+                }
+            }
         }
         self.print_pager().expect("Unable to print the pager");
     }
 
     fn handle_view_event(&mut self, event: ViewEvent) {
+        let is_quit_pending = std::mem::take(&mut self.is_quit_pending);
+        self.status_message.clear();
         match event {
             ViewEvent::NextLine => self.next_line(),
+            ViewEvent::NextPage => self.scroll_page(true, false),
+            ViewEvent::PrevPage => self.scroll_page(false, false),
+            ViewEvent::NextHalfPage => self.scroll_page(true, true),
+            ViewEvent::PrevHalfPage => self.scroll_page(false, true),
             ViewEvent::PrevLine => self.prev_line(),
+            ViewEvent::ScrollLeft => self.scroll_left(),
+            ViewEvent::ScrollRight => self.scroll_right(),
+            ViewEvent::ToggleWrap => self.toggle_wrap(),
+            ViewEvent::ToggleRelativeTime => self.toggle_relative_time(),
             ViewEvent::Quit => self.exit(),
+            ViewEvent::QuitPrefix => {
+                if is_quit_pending {
+                    self.exit();
+                } else {
+                    self.is_quit_pending = true;
+                }
+            }
             ViewEvent::Resize(columns, rows) => self.resize(columns, rows),
             ViewEvent::GotoPageBegin => self.goto_page_begin(),
             ViewEvent::GotoPageEnd => self.goto_page_end(),
             ViewEvent::EnterSearchMode => self.enter_search_mode(),
-            ViewEvent::SearchNext => self.search_next(true),
-            ViewEvent::SearchPrev => self.search_prev(),
+            ViewEvent::EnterGotoDateMode => self.enter_goto_date_mode(),
+            ViewEvent::OpenDate => self.open_current_date(),
+            ViewEvent::SearchNext => self.search_next_in_all_days(true),
+            ViewEvent::SearchPrev => self.search_prev_in_all_days(),
+            ViewEvent::NextEntry => {
+                self.next_entry();
+                self.update_loaded_days();
+            }
+            ViewEvent::PrevEntry => {
+                self.prev_entry();
+                self.update_loaded_days();
+            }
             _ => {}
         }
 
@@ -208,6 +751,23 @@ impl PagingAllPager {
         }
 
         self.set_begin_line_index(page_range.begin + 1);
+        self.update_loaded_days();
+    }
+
+    /// Scrolls by a page, or by half a page if `half` is true, keeping `display.scroll_overlap`
+    /// lines of the current page.
+    fn scroll_page(&mut self, forward: bool, half: bool) {
+        let page_height = self.page_height();
+        let distance = scroll_distance(page_height, self.display_config.scroll_overlap, half);
+        let target = scroll_target(
+            self.page_range().begin,
+            distance,
+            forward,
+            self.total_content_lines(),
+            page_height,
+        );
+        self.set_begin_line_index(target);
+        self.update_loaded_days();
     }
 
     pub fn prev_line(&mut self) {
@@ -216,36 +776,53 @@ impl PagingAllPager {
             return;
         }
         self.set_begin_line_index(page_range_begin - 1);
+        self.update_loaded_days();
     }
 
-    fn update_log_items(&mut self) {
-        let content = self.content();
+    /// Reads the dates of the log files, keeping the most recent `day_limit` ones.
+    fn read_all_dates(&mut self) {
+        self.all_dates = get_log_dates(&self.log_dir_path, self.exclude_today).unwrap();
+        if let Some(day_limit) = self.day_limit {
+            let skipped_days = self.all_dates.len().saturating_sub(day_limit);
+            self.all_dates.drain(..skipped_days);
+        }
+    }
 
-        self.log_item_list = LogItemList::from_str(&content).expect("Invalid log file");
+    fn update_log_items(&mut self) {
+        self.read_all_dates();
+        self.loaded_days.clear();
+        self.loaded_days_begin = 0;
         self.update_colored_lines();
+        if !self.all_dates.is_empty() {
+            self.load_only_day(0);
+        }
+        self.update_loaded_days();
+    }
+
+    /// The number of content lines that fit in a page.
+    fn page_height(&self) -> usize {
+        let terminal_total_rows = self.terminal_total_rows as usize;
+        if terminal_total_rows <= 2 {
+            1
+        } else {
+            terminal_total_rows - 2
+        }
     }
 
     fn page_range(&self) -> Range {
-        let terminal_total_rows = self.terminal_total_rows;
         let page_range_begin =
             get_line_index_by_char_index(&self.colored_lines, self.begin_char_index).unwrap_or(0);
-        let page_range_end = if terminal_total_rows <= 2 {
+        let page_range_end = if self.terminal_total_rows <= 2 {
             page_range_begin + 1
         } else {
             min(
                 self.total_content_lines(),
-                page_range_begin + terminal_total_rows as usize - 2,
+                page_range_begin + self.page_height(),
             )
         };
         Range::new(page_range_begin, page_range_end)
     }
 
-    fn prepare_run(&self) {
-        enable_raw_mode().expect("Failed to enable raw mode");
-        execute!(stdout(), crossterm::terminal::EnterAlternateScreen)
-            .expect("Unable to enter alternate screen");
-    }
-
     fn print_colored_file_content(&self, stdout: &mut Stdout) -> Result<(), std::io::Error> {
         let terminal_total_rows = self.terminal_total_rows;
         if terminal_total_rows == 0 {
@@ -254,13 +831,22 @@ impl PagingAllPager {
 
         let range = self.page_range();
 
-        let colored_lines = &self.colored_lines;
+        let colored_lines = &self.colored_lines[range.begin..range.end];
 
-        for i in range.begin..range.end {
-            if i != range.begin {
+        for (i, line) in colored_lines.iter().enumerate() {
+            if i != 0 {
                 queue!(stdout, cursor::MoveToNextLine(1))?;
             }
-            queue!(stdout, Print(&colored_lines[i]))?;
+            let width = self.terminal_total_cols as usize;
+            let line = slice_colored_line(line, self.col_offset, width);
+            if i == 0 && self.display_config.highlight_current_line {
+                queue!(
+                    stdout,
+                    Print(set_line_background(&line, width, CURRENT_LINE_BACKGROUND))
+                )?;
+            } else {
+                queue!(stdout, Print(line))?;
+            }
         }
 
         Ok(())
@@ -274,19 +860,52 @@ impl PagingAllPager {
             cursor::MoveTo(0, 0),
             cursor::Hide
         )?;
-        self.print_colored_file_content(&mut stdout)?;
+        if self.colored_lines.is_empty() {
+            self.print_empty_message(&mut stdout)?;
+        } else {
+            self.print_colored_file_content(&mut stdout)?;
+        }
 
-        match self.mode {
-            PagerMode::Search => {
-                self.print_search_pattern_input(&mut stdout)?;
-            }
-            _ => {}
+        if self.mode == PagerMode::Search {
+            self.print_search_pattern_input(&mut stdout)?;
+        } else if self.mode == PagerMode::GotoDate {
+            queue_goto_date_input(&mut stdout, self.terminal_total_rows, &self.goto_date_input)?;
+        } else if !self.status_message.is_empty() {
+            crossterm::queue!(
+                stdout,
+                cursor::MoveTo(0, self.terminal_total_rows.saturating_sub(1)),
+                Print(self.status_message.as_str().white().on(self.theme.error))
+            )?;
         }
+        self.print_clock(&mut stdout)?;
 
         stdout.flush()?;
         Ok(())
     }
 
+    /// Prints the clock of `display.clock` in the view mode. It's hidden while a status
+    /// message is shown, which it could cover.
+    fn print_clock(&self, stdout: &mut Stdout) -> Result<(), std::io::Error> {
+        if self.display_config.clock
+            && self.mode == PagerMode::View
+            && self.status_message.is_empty()
+        {
+            queue_clock(stdout, self.terminal_total_cols, self.terminal_total_rows)?;
+        }
+        Ok(())
+    }
+
+    /// Prints a hint in the middle of the screen when there is no log item to show.
+    fn print_empty_message(&self, stdout: &mut Stdout) -> Result<(), std::io::Error> {
+        let message = empty_message();
+        let (col, row) = centered_position(
+            message.chars().count(),
+            self.terminal_total_cols,
+            self.terminal_total_rows,
+        );
+        queue!(stdout, cursor::MoveTo(col, row), Print(message.dark_grey()))
+    }
+
     fn print_search_pattern_input(&self, stdout: &mut Stdout) -> Result<(), std::io::Error> {
         let terminal_total_rows = self.terminal_total_rows;
         crossterm::queue!(
@@ -295,16 +914,31 @@ impl PagingAllPager {
             Print('/'),
             Print(&self.search_pattern_input)
         )?;
+        if self.search_pattern_input.is_empty() {
+            let hint = format!("(prefix with `{}` to search the dates)", DATE_SEARCH_PREFIX);
+            crossterm::queue!(stdout, Print(hint.dark_grey()))?;
+        }
 
         Ok(())
     }
 
     pub fn run(&mut self) {
-        self.prepare_run();
+        let _terminal_guard = TerminalGuard::new(self.display_config.alternate_screen)
+            .expect("Unable to prepare the terminal");
         self.print_pager().expect("Print pager");
 
         while !self.is_exit {
-            let crossterm_event = crossterm::event::read().expect("Unable to read the event");
+            let crossterm_event =
+                match read_event(self.display_config.clock).expect("Unable to read the event") {
+                    Some(crossterm_event) => crossterm_event,
+                    None => {
+                        let mut stdout = stdout();
+                        self.print_clock(&mut stdout)
+                            .and_then(|_| stdout.flush())
+                            .expect("Unable to print the clock");
+                        continue;
+                    }
+                };
             match self.mode {
                 PagerMode::View => {
                     let event = ViewEvent::from_crossterm_event(crossterm_event);
@@ -315,12 +949,12 @@ impl PagingAllPager {
                     let event = SearchEvent::from_crossterm_event(crossterm_event);
                     self.handle_search_event(event);
                 }
+                PagerMode::GotoDate => {
+                    let event = GotoDateEvent::from_crossterm_event(crossterm_event);
+                    self.handle_goto_date_event(event);
+                }
             }
         }
-
-        crate::utils::terminal::restore_terminal().expect("Unable to restore the terminal");
-
-        disable_raw_mode().expect("Unable to diable raw mode");
     }
 
     pub fn total_content_lines(&self) -> usize {
@@ -330,14 +964,270 @@ impl PagingAllPager {
 
 impl Pager for PagingAllPager {
     fn begin_line_index(&self) -> usize {
-        get_line_index_by_char_index(&self.colored_lines, self.begin_char_index).unwrap()
+        get_line_index_by_char_index(&self.colored_lines, self.begin_char_index).unwrap_or(0)
     }
 
     fn colored_lines(&self) -> &Vec<String> {
         &self.colored_lines
     }
 
+    fn line_item_indices(&self) -> &Vec<Option<usize>> {
+        &self.line_item_indices
+    }
+
     fn set_begin_line_index(&mut self, line_index: usize) {
         self.begin_char_index = get_char_index_by_line_index(&self.colored_lines, line_index);
     }
+
+    fn search_background(&self) -> Color {
+        self.theme.search_match
+    }
+}
+
+fn empty_message() -> String {
+    format!("No log entries yet. Use `{} write` to add one.", PKG_NAME)
+}
+
+/// Returns the line shown before the log items of `date`, e.g.
+/// `─── 2024-05-12 Sun (3 entries) ───`.
+fn day_separator(date: NaiveDate, weekday: &str, entries: usize) -> String {
+    let entries = if entries == 1 {
+        "1 entry".to_owned()
+    } else {
+        format!("{} entries", entries)
+    };
+    format!("─── {} {} ({}) ───", date, weekday, entries)
+}
+
+/// Returns the column and the row where a line of `width` columns is centered on the screen.
+fn centered_position(
+    width: usize,
+    terminal_total_cols: u16,
+    terminal_total_rows: u16,
+) -> (u16, u16) {
+    let col = (terminal_total_cols as usize).saturating_sub(width) / 2;
+    (col as u16, terminal_total_rows / 2)
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use chrono::{Days, NaiveDate};
+
+    use crate::{config::construct_log_file_path, log_pager::search::SearchField};
+
+    use super::{centered_position, day_separator, PagingAllPager};
+
+    const TOTAL_DAYS: u64 = 5000;
+
+    fn write_synthetic_days(log_dir_path: &Path) {
+        let first_date = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+        for day in 0..TOTAL_DAYS {
+            let date = first_date + Days::new(day);
+            let content = format!("[{} 12:00] Synthetic entry {}\n", date, day);
+            std::fs::write(construct_log_file_path(log_dir_path, &date), content).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_day_separator() {
+        let date = NaiveDate::from_ymd_opt(2024, 5, 12).unwrap();
+        assert_eq!(
+            day_separator(date, "Sun", 3),
+            "─── 2024-05-12 Sun (3 entries) ───"
+        );
+        assert_eq!(
+            day_separator(date, "Sun", 1),
+            "─── 2024-05-12 Sun (1 entry) ───"
+        );
+
+        let log_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            construct_log_file_path(log_dir.path(), &date),
+            "[2024-05-12 08:00] first\n[2024-05-12 09:00]\n[2024-05-12 10:00] third\n",
+        )
+        .unwrap();
+        let mut pager = PagingAllPager::new(log_dir.path().to_path_buf());
+        pager.resize(80, 24);
+        assert!(pager.colored_lines[0].contains("(3 entries)"));
+        pager.display_config.hide_empty_entries = true;
+        pager.update_colored_lines();
+        assert!(pager.colored_lines[0].contains("(2 entries)"));
+    }
+
+    #[test]
+    fn test_set_search() {
+        let log_dir = tempfile::tempdir().unwrap();
+        write_synthetic_days(log_dir.path());
+        let mut pager = PagingAllPager::new(log_dir.path().to_path_buf());
+        pager.resize(80, 24);
+
+        pager.set_search("entry 4321$");
+        assert!(pager.search_pattern.is_some());
+        let begin = pager.page_range().begin;
+        assert!(pager.colored_lines[begin].contains("4321"));
+        assert!(pager.status_message.is_empty());
+
+        pager.set_search("(");
+        assert!(pager.search_pattern.is_none());
+        assert_eq!(pager.status_message, "invalid pattern: (");
+    }
+
+    #[test]
+    fn test_sort_entries() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 5, 12).unwrap();
+        std::fs::write(
+            construct_log_file_path(log_dir.path(), &date),
+            "[2024-05-12 10:00] a\n[2024-05-12 09:00] b\n[2024-05-12 10:00] c\n\
+             [2024-05-12 09:00] d\n[2024-05-12 10:00] e\n",
+        )
+        .unwrap();
+        let mut pager = PagingAllPager::new(log_dir.path().to_path_buf());
+        pager.resize(80, 24);
+        let contents = |pager: &PagingAllPager| -> Vec<String> {
+            pager.loaded_days[0]
+                .iter()
+                .map(|item| item.content().to_owned())
+                .collect()
+        };
+        assert_eq!(contents(&pager), ["a", "b", "c", "d", "e"]);
+
+        // The entries with the same time keep their order in the file
+        let mut display_config = pager.display_config.clone();
+        display_config.sort_entries = true;
+        pager.set_display_config(display_config);
+        assert_eq!(contents(&pager), ["b", "d", "a", "c", "e"]);
+        assert!(pager.colored_lines[1].contains("09:00"));
+        assert!(pager.colored_lines[1].ends_with(" b"));
+        assert!(pager.colored_lines[5].ends_with(" e"));
+    }
+
+    #[test]
+    fn test_goto_date() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let first_date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        for day in [0, 5, 10] {
+            let date = first_date + Days::new(day);
+            let content = format!("[{} 12:00] entry {}\n", date, day);
+            std::fs::write(construct_log_file_path(log_dir.path(), &date), content).unwrap();
+        }
+        let mut pager = PagingAllPager::new(log_dir.path().to_path_buf());
+        pager.resize(80, 3);
+
+        pager.goto_date_input = "2024-05-06".to_owned();
+        pager.confirm_goto_date();
+        assert!(pager.colored_lines[pager.page_range().begin].contains("2024-05-06"));
+
+        // A date without a log file goes to the next day with one
+        pager.goto_date_input = "2024-05-08".to_owned();
+        pager.confirm_goto_date();
+        assert!(pager.colored_lines[pager.page_range().begin].contains("2024-05-11"));
+
+        pager.goto_date_input = "foo".to_owned();
+        pager.confirm_goto_date();
+        assert_eq!(pager.status_message, "invalid date: foo");
+        assert!(pager.goto_date_input.is_empty());
+    }
+
+    #[test]
+    fn test_day_limit() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let first_date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        for day in 0..10 {
+            let date = first_date + Days::new(day);
+            let content = format!("[{} 12:00] entry {}\n", date, day);
+            std::fs::write(construct_log_file_path(log_dir.path(), &date), content).unwrap();
+        }
+        let mut pager = PagingAllPager::new(log_dir.path().to_path_buf());
+        pager.resize(80, 24);
+        pager.set_day_limit(Some(3));
+        assert_eq!(pager.all_dates.len(), 3);
+        assert_eq!(pager.all_dates[0], first_date + Days::new(7));
+        assert!(pager.colored_lines[0].contains("2024-05-08"));
+
+        pager.set_day_limit(Some(20));
+        assert_eq!(pager.all_dates.len(), 10);
+        pager.set_day_limit(Some(0));
+        assert!(pager.colored_lines.is_empty());
+    }
+
+    #[test]
+    fn test_empty_log_dir() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let mut pager = PagingAllPager::new(log_dir.path().to_path_buf());
+        pager.resize(80, 24);
+        assert!(pager.colored_lines.is_empty());
+        assert_eq!(centered_position(20, 80, 24), (30, 12));
+        assert_eq!(centered_position(100, 80, 24), (0, 12));
+    }
+
+    #[test]
+    fn test_lazy_loading() {
+        let log_dir = tempfile::tempdir().unwrap();
+        write_synthetic_days(log_dir.path());
+        let mut pager = PagingAllPager::new(log_dir.path().to_path_buf());
+        pager.resize(80, 24);
+
+        // Only the days around the first page are loaded.
+        assert_eq!(pager.all_dates.len(), TOTAL_DAYS as usize);
+        assert_eq!(pager.loaded_days_begin, 0);
+        assert!(pager.loaded_days.len() < 100);
+        assert_eq!(pager.page_range().begin, 0);
+
+        // Scrolling loads the following days and unloads the ones far behind.
+        // Each day takes two lines, so the 500th day is at the top after scrolling 1000 lines.
+        for _ in 0..1000 {
+            pager.next_line();
+        }
+        let date = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap() + Days::new(500);
+        assert!(pager.colored_lines[pager.page_range().begin].contains(&date.to_string()));
+        assert!(pager.loaded_days_begin > 0);
+        assert!(pager.loaded_days.len() < 100);
+
+        pager.goto_page_end();
+        assert_eq!(pager.loaded_days_end(), TOTAL_DAYS as usize);
+        assert!(pager.loaded_days.len() < 100);
+        assert_eq!(pager.page_range().end, pager.total_content_lines());
+
+        pager.goto_page_begin();
+        assert_eq!(pager.loaded_days_begin, 0);
+        assert_eq!(pager.page_range().begin, 0);
+
+        // Each line knows the date of its log item
+        assert_eq!(pager.line_dates.len(), pager.total_content_lines());
+        let date = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap() + Days::new(100);
+        pager.goto_date(date);
+        assert_eq!(pager.line_dates[pager.page_range().begin], date);
+        assert_eq!(pager.line_dates[pager.page_range().begin + 1], date);
+    }
+
+    #[test]
+    fn test_search_in_unloaded_days() {
+        let log_dir = tempfile::tempdir().unwrap();
+        write_synthetic_days(log_dir.path());
+        let mut pager = PagingAllPager::new(log_dir.path().to_path_buf());
+        pager.resize(80, 24);
+
+        pager.search_pattern = regex::Regex::new("entry 3000$").ok();
+        pager.update_colored_lines();
+        pager.search_next_in_all_days(false);
+        let page_range = pager.page_range();
+        assert!(pager.colored_lines[page_range.begin].contains("entry 3000"));
+        assert!(pager.loaded_days.len() < 100);
+
+        pager.search_pattern = regex::Regex::new("entry 10$").ok();
+        pager.update_colored_lines();
+        pager.search_prev_in_all_days();
+        let page_range = pager.page_range();
+        assert!(pager.colored_lines[page_range.begin].contains("entry 10"));
+
+        pager.search_field = SearchField::Date;
+        pager.search_pattern = regex::Regex::new("^2010-01-01").ok();
+        pager.update_colored_lines();
+        pager.search_next_in_all_days(false);
+        let page_range = pager.page_range();
+        assert!(pager.colored_lines[page_range.begin].contains("2010-01-01"));
+    }
 }
@@ -1,42 +1,69 @@
 use std::borrow::Cow;
 
-use crossterm::style::Stylize;
+use crossterm::style::{Color, Stylize};
+
+use crate::log_item::LogItem;
 
 use super::pager::Pager;
 
+/// The prefix of a search input that matches the dates of the log items
+/// instead of their content, e.g. `date:2024-05`.
+pub const DATE_SEARCH_PREFIX: &str = "date:";
+
+/// The part of the log items that a search pattern is matched against.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum SearchField {
+    #[default]
+    Content,
+    Date,
+}
+
+/// Splits a search input into the searched field and the pattern.
+pub fn parse_search_input(input: &str) -> (SearchField, &str) {
+    match input.strip_prefix(DATE_SEARCH_PREFIX) {
+        Some(pattern) => (SearchField::Date, pattern),
+        None => (SearchField::Content, input),
+    }
+}
+
+/// Returns whether the field of `log_item` has a non-empty match of `regex`, like the
+/// results marked by `mark_search_result`. The date is matched in the `%Y-%m-%d %H:%M` format.
+pub fn log_item_matches(field: SearchField, regex: &regex::Regex, log_item: &LogItem) -> bool {
+    let has_non_empty_match = |s: &str| regex.find_iter(s).any(|m| !m.is_empty());
+    match field {
+        SearchField::Content => has_non_empty_match(log_item.content()),
+        SearchField::Date => {
+            has_non_empty_match(&log_item.date_time().format("%Y-%m-%d %H:%M").to_string())
+        }
+    }
+}
+
 pub trait Search {
-    fn search_next(&mut self, skip_current_line: bool);
-    fn search_prev(&mut self);
+    /// Moves the page to the next line containing a search result.
+    /// Returns `false` if there is no such line.
+    fn search_next(&mut self, skip_current_line: bool) -> bool;
+    /// Moves the page to the previous line containing a search result.
+    /// Returns `false` if there is no such line.
+    fn search_prev(&mut self) -> bool;
 }
 impl<T> Search for T
 where
     T: Pager,
 {
-    fn search_next(&mut self, skip_current_line: bool) {
-        let target_str: String = "\0"
-            .on_white()
-            .to_string()
-            .split_once('\0')
-            .unwrap()
-            .1
-            .to_owned();
+    fn search_next(&mut self, skip_current_line: bool) -> bool {
+        let target_str = search_result_marker(self.search_background());
         let lines_to_skip = self.begin_line_index() + if skip_current_line { 1 } else { 0 };
         for (line_index, line) in self.colored_lines().iter().enumerate().skip(lines_to_skip) {
             if line.contains(&target_str) {
                 self.set_begin_line_index(line_index);
-                break;
+                return true;
             }
         }
+        false
     }
 
-    fn search_prev(&mut self) {
-        let target_str: String = "\0"
-            .on_white()
-            .to_string()
-            .split_once('\0')
-            .unwrap()
-            .1
-            .to_owned();
+    fn search_prev(&mut self) -> bool {
+        let target_str = search_result_marker(self.search_background());
         let lines_to_take: usize = self.begin_line_index();
         for (line_index, line) in self
             .colored_lines()
@@ -47,19 +74,149 @@ where
         {
             if line.contains(&target_str) {
                 self.set_begin_line_index(line_index);
-                break;
+                return true;
             }
         }
+        false
     }
 }
 
-pub fn mark_search_result<'h>(regex: &regex::Regex, s: &'h str) -> Cow<'h, str> {
-    // Use regular expressions to replace matching parts
-    let result = regex.replace_all(s, |caps: &regex::Captures| {
-        // Get the matched text
+/// Returns the escape sequence that starts a search result highlighted with the `background`
+/// by `highlight_search_result`, which `Search` looks for in the colored lines.
+pub fn search_result_marker(background: Color) -> String {
+    let styled = "\0".on(background).to_string();
+    styled.split_once('\0').unwrap().0.to_owned()
+}
+
+/// Highlights a search result with the `background`. Each line is highlighted separately, so
+/// that every line of a multi-line result is found by `Search` after the content is split into lines.
+pub fn highlight_search_result(matched_text: &str, background: Color) -> String {
+    matched_text
+        .split('\n')
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                line.black().on(background).to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Highlights the matches of `regex` in `s` with the `background`. Empty matches,
+/// e.g. of `a*`, are skipped, since there is nothing to highlight.
+pub fn mark_search_result<'h>(regex: &regex::Regex, s: &'h str, background: Color) -> Cow<'h, str> {
+    if !regex.find_iter(s).any(|m| !m.is_empty()) {
+        return Cow::Borrowed(s);
+    }
+    regex.replace_all(s, |caps: &regex::Captures| {
         let matched_text = caps.get(0).map(|m| m.as_str()).unwrap_or("");
-        // Highlight the matching text
-        matched_text.black().on_white().to_string()
-    });
-    result
+        highlight_search_result(matched_text, background)
+    })
+}
+
+/// Highlights the non-empty matches of `search_pattern` in `s` with the `background`,
+/// and passes the text between them to `mark_rest`, e.g. to style the syntax.
+///
+/// The search results themselves aren't passed to `mark_rest`, since nested styles
+/// would be reset by the inner style and break the detection of the search results.
+pub fn mark_search_result_and_rest(
+    search_pattern: Option<&regex::Regex>,
+    s: &str,
+    background: Color,
+    mark_rest: impl Fn(&str) -> String,
+) -> String {
+    let Some(regex) = search_pattern else {
+        return mark_rest(s);
+    };
+
+    let mut ret = String::new();
+    let mut last_match_end = 0;
+    for matched in regex.find_iter(s).filter(|m| !m.is_empty()) {
+        ret += &mark_rest(&s[last_match_end..matched.start()]);
+        ret += &highlight_search_result(matched.as_str(), background);
+        last_match_end = matched.end();
+    }
+    ret += &mark_rest(&s[last_match_end..]);
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use std::borrow::Cow;
+
+    use chrono::NaiveDateTime;
+    use crossterm::style::{Color, Stylize};
+    use regex::Regex;
+
+    use crate::log_item::LogItem;
+
+    use super::{log_item_matches, mark_search_result, parse_search_input, SearchField};
+
+    #[test]
+    fn test_parse_search_input() {
+        assert_eq!(
+            parse_search_input("date:2024-05"),
+            (SearchField::Date, "2024-05")
+        );
+        assert_eq!(
+            parse_search_input("meeting"),
+            (SearchField::Content, "meeting")
+        );
+        assert_eq!(
+            parse_search_input("update:date"),
+            (SearchField::Content, "update:date")
+        );
+    }
+
+    #[test]
+    fn test_mark_search_result() {
+        let regex = Regex::new("a*").unwrap();
+        assert!(matches!(
+            mark_search_result(&regex, "xyz", Color::White),
+            Cow::Borrowed("xyz")
+        ));
+        assert_eq!(
+            mark_search_result(&regex, "xaay", Color::White),
+            format!("x{}y", "aa".black().on_white())
+        );
+
+        let regex = Regex::new("").unwrap();
+        assert_eq!(mark_search_result(&regex, "xyz", Color::White), "xyz");
+
+        let regex = Regex::new("foo|ba+r").unwrap();
+        assert_eq!(
+            mark_search_result(&regex, "foo baar bz", Color::White),
+            format!(
+                "{} {} bz",
+                "foo".black().on_white(),
+                "baar".black().on_white()
+            )
+        );
+
+        // Each line of a multi-line match is highlighted separately
+        let regex = Regex::new("(?s)b.*").unwrap();
+        assert_eq!(
+            mark_search_result(&regex, "ab\n\nc", Color::White),
+            format!("a{}\n\n{}", "b".black().on_white(), "c".black().on_white())
+        );
+    }
+
+    #[test]
+    fn test_log_item_matches() {
+        let date_time =
+            NaiveDateTime::parse_from_str("2024-05-01 08:00", "%Y-%m-%d %H:%M").unwrap();
+        let log_item = LogItem::new(date_time, "xyz");
+        let matches = |pattern: &str| {
+            log_item_matches(
+                SearchField::Content,
+                &Regex::new(pattern).unwrap(),
+                &log_item,
+            )
+        };
+        assert!(!matches("a*"));
+        assert!(!matches(""));
+        assert!(matches("a|y"));
+    }
 }
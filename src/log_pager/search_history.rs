@@ -0,0 +1,85 @@
+/// The confirmed search patterns of a pager, recalled with the up and down arrows.
+#[derive(Default)]
+pub struct SearchHistory {
+    patterns: Vec<String>,
+    /// The index in `patterns` of the recalled pattern, or `None` if nothing is recalled.
+    position: Option<usize>,
+    /// The input before recalling, restored after going past the newest pattern.
+    draft: String,
+}
+
+impl SearchHistory {
+    /// Adds a confirmed pattern to the history and stops recalling.
+    /// Empty patterns and repeats of the newest pattern are ignored.
+    pub fn push(&mut self, pattern: &str) {
+        self.reset();
+        if pattern.is_empty() || self.patterns.last().is_some_and(|last| last == pattern) {
+            return;
+        }
+        self.patterns.push(pattern.to_owned());
+    }
+
+    /// Stops recalling, so that the next `recall_prev` starts from the newest pattern.
+    pub fn reset(&mut self) {
+        self.position = None;
+        self.draft.clear();
+    }
+
+    /// Returns the pattern before the recalled one, or the newest pattern if nothing
+    /// is recalled yet. `input` is kept to be restored by `recall_next`.
+    pub fn recall_prev(&mut self, input: &str) -> Option<&str> {
+        let position = match self.position {
+            Some(position) => position.saturating_sub(1),
+            None => {
+                if self.patterns.is_empty() {
+                    return None;
+                }
+                self.draft = input.to_owned();
+                self.patterns.len() - 1
+            }
+        };
+        self.position = Some(position);
+        Some(&self.patterns[position])
+    }
+
+    /// Returns the pattern after the recalled one, or the input before recalling
+    /// if the newest pattern is recalled.
+    pub fn recall_next(&mut self) -> Option<&str> {
+        let position = self.position?;
+        if position + 1 < self.patterns.len() {
+            self.position = Some(position + 1);
+            Some(&self.patterns[position + 1])
+        } else {
+            self.position = None;
+            Some(&self.draft)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SearchHistory;
+
+    #[test]
+    fn test_search_history() {
+        let mut history = SearchHistory::default();
+        assert_eq!(history.recall_prev("draft"), None);
+        assert_eq!(history.recall_next(), None);
+
+        history.push("foo");
+        history.push("bar");
+        history.push("bar");
+        history.push("");
+
+        assert_eq!(history.recall_prev("draft"), Some("bar"));
+        assert_eq!(history.recall_prev("bar"), Some("foo"));
+        assert_eq!(history.recall_prev("foo"), Some("foo"));
+        assert_eq!(history.recall_next(), Some("bar"));
+        assert_eq!(history.recall_next(), Some("draft"));
+        assert_eq!(history.recall_next(), None);
+
+        history.recall_prev("");
+        history.reset();
+        assert_eq!(history.recall_prev("new"), Some("bar"));
+    }
+}
@@ -1,27 +1,52 @@
 use std::borrow::Cow;
 use std::cmp::min;
-use std::io::{stdout, Stdout, Write};
+use std::io::{stdout, Read, Stdout, Write};
 use std::path::PathBuf;
 use std::str::FromStr;
 
 use super::command;
+use super::entry_navigation::EntryNavigation;
 use super::events::command_event::CommandEvent;
+use super::events::goto_date_event::GotoDateEvent;
 use super::events::search_event::SearchEvent;
 use super::events::view_event::ViewEvent;
+use super::highlight::{mark_highlights, HIGHLIGHT_COLORS};
+use super::page_scroll::{scroll_distance, scroll_target};
 use super::pager::Pager;
 use super::pager_mode::PagerMode;
 use super::range::Range;
 use super::search::Search;
-use super::utils::{get_char_index_by_line_index, get_line_index_by_char_index};
-use chrono::{Datelike, Days, NaiveDate};
-use crossterm::style::{ContentStyle, Print, PrintStyledContent, StyledContent, Stylize};
-use crossterm::terminal::{disable_raw_mode, enable_raw_mode, Clear};
+use super::search_history::SearchHistory;
+use super::utils::{
+    get_added_line_flags, get_char_index_by_line_index, get_line_index_by_char_index, queue_clock,
+    queue_goto_date_input, read_event, relative_time_text, set_line_background, slice_colored_line,
+    wrap_line, CLOCK_WIDTH, CURRENT_LINE_BACKGROUND,
+};
+use chrono::{Days, NaiveDate};
+use crossterm::style::{Color, ContentStyle, Print, PrintStyledContent, StyledContent, Stylize};
+use crossterm::terminal::Clear;
 use crossterm::{cursor, execute, queue};
 
-use crate::config::construct_log_file_path;
+use crate::config::{construct_log_file_path, get_all_log_dates, DisplayConfig, LogConfig};
+use crate::error::Error;
 use crate::log_item::{LogItem, LogItemList};
-use crate::log_pager::search::mark_search_result;
-use crate::utils::time::get_today_date;
+use crate::log_pager::search::{
+    mark_search_result, mark_search_result_and_rest, parse_search_input, SearchField,
+    DATE_SEARCH_PREFIX,
+};
+use crate::log_pager::syntax::{mark_search_result_and_syntax, mark_syntax};
+use crate::theme::Theme;
+use crate::tidy::parse_log_content;
+use crate::utils::clipboard::copy_to_clipboard;
+use crate::utils::editor::{edit_file, find_editor};
+use crate::utils::fs::{open_locked, overwrite_file, read_to_string_lossy, remove_file_if_blank};
+use crate::utils::template::expand_template;
+use crate::utils::terminal::TerminalGuard;
+use crate::utils::time::{get_today_date, parse_date_from_str, weekday_name};
+
+/// Shown at the bottom in the view mode when there is no message, if `display.show_hints` is on.
+const KEY_HINTS: &str =
+    "q quit  / search  : command  e edit  h/l day  D go to date  d delete  y copy";
 
 pub struct SingleDatePager {
     date: NaiveDate,
@@ -30,16 +55,54 @@ pub struct SingleDatePager {
     /// The index of the first character of the current page in the log file.
     /// White space characters are ignored when calculating the index.
     begin_char_index: usize,
+    /// The number of display columns the page is scrolled to the right.
+    col_offset: usize,
+    /// Whether the lines longer than the page width are wrapped.
+    /// Initialized by `display.wrap`, and toggled in the view mode.
+    wrap: bool,
     bottom_message: StyledContent<String>,
     log_item_list: LogItemList,
+    /// The content of the log file as stored, shown instead of the parsed
+    /// log items if `show_raw_content` is true.
+    raw_content: String,
+    show_raw_content: bool,
     terminal_total_rows: u16,
     terminal_total_cols: u16,
     colored_lines: Vec<String>,
+    line_item_indices: Vec<Option<usize>>,
     mode: PagerMode,
     is_exit: bool,
+    /// Whether the last key was the first `Z` of `ZZ`.
+    is_quit_pending: bool,
+    /// The index of the log item to delete if the next key is `y`.
+    pending_deletion: Option<usize>,
     command_buffer: String,
     search_pattern: Option<regex::Regex>,
+    search_field: SearchField,
     search_pattern_input: String,
+    search_history: SearchHistory,
+    /// The patterns highlighted by the `:hl` command, each in its color, until `:nohl`.
+    highlights: Vec<(regex::Regex, Color)>,
+    /// The date typed in the goto-date mode.
+    goto_date_input: String,
+    display_config: DisplayConfig,
+    theme: Theme,
+    prompt_format: String,
+    /// The formats of the date accepted by the `:date` command.
+    date_input_formats: Vec<String>,
+    /// Whether a date without a year after today is in the previous year in the `:date` and `:e` commands.
+    yearless_dates_in_past: bool,
+    /// `log.editor`, or empty for the editor of `$VISUAL` or `$EDITOR`.
+    editor: String,
+    /// The content shown instead of the log file, e.g. read from stdin.
+    /// The day navigation and the editing are disabled if it's set.
+    piped_content: Option<String>,
+    /// The clipboard that the log items are copied to, created on the first copy and kept
+    /// so that the copied text stays available while the pager runs.
+    clipboard: Option<arboard::Clipboard>,
+    /// Whether each line of the log items has been added by the last edit.
+    /// The added lines are marked until the next view event.
+    added_line_flags: Option<Vec<bool>>,
 }
 
 impl SingleDatePager {
@@ -53,16 +116,36 @@ impl SingleDatePager {
             log_dir_path,
             verbose: false,
             begin_char_index: 0,
+            col_offset: 0,
+            wrap: true,
             bottom_message: message,
             log_item_list: LogItemList::new(),
+            raw_content: String::new(),
+            show_raw_content: false,
             terminal_total_rows,
             terminal_total_cols,
             colored_lines: Vec::new(),
+            line_item_indices: Vec::new(),
             mode: PagerMode::View,
             is_exit: false,
+            is_quit_pending: false,
+            pending_deletion: None,
             command_buffer: String::new(),
             search_pattern: None,
+            search_field: SearchField::default(),
             search_pattern_input: String::new(),
+            search_history: SearchHistory::default(),
+            highlights: Vec::new(),
+            goto_date_input: String::new(),
+            display_config: DisplayConfig::default(),
+            theme: Theme::default(),
+            prompt_format: LogConfig::default().prompt_format,
+            date_input_formats: LogConfig::default().date_input_formats,
+            yearless_dates_in_past: LogConfig::default().yearless_dates_in_past,
+            editor: String::new(),
+            added_line_flags: None,
+            piped_content: None,
+            clipboard: None,
         };
 
         ret.update_log_items();
@@ -75,48 +158,94 @@ impl SingleDatePager {
         self.verbose = value;
     }
 
+    pub fn set_display_config(&mut self, display_config: DisplayConfig) {
+        self.wrap = display_config.wrap;
+        self.display_config = display_config;
+        self.update_colored_lines();
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        self.update_colored_lines();
+    }
+
+    pub fn set_prompt_format(&mut self, prompt_format: String) {
+        self.prompt_format = prompt_format;
+    }
+
+    pub fn set_date_input_formats(&mut self, date_input_formats: Vec<String>) {
+        self.date_input_formats = date_input_formats;
+    }
+
+    pub fn set_yearless_dates_in_past(&mut self, yearless_dates_in_past: bool) {
+        self.yearless_dates_in_past = yearless_dates_in_past;
+    }
+
+    pub fn set_editor(&mut self, editor: String) {
+        self.editor = editor;
+    }
+
     pub fn total_content_lines(&self) -> usize {
         self.colored_lines.len()
     }
 
+    /// The number of lines of the log content on a page.
+    fn page_height(&self) -> usize {
+        let terminal_total_rows = self.terminal_total_rows as usize;
+        if terminal_total_rows <= 2 {
+            1
+        } else {
+            terminal_total_rows - 2
+        }
+    }
+
     fn page_range(&self) -> Range {
-        let terminal_total_rows = self.terminal_total_rows;
         let page_range_begin =
             get_line_index_by_char_index(&self.colored_lines, self.begin_char_index).unwrap_or(0);
-        let page_range_end = if terminal_total_rows <= 2 {
+        let page_range_end = if self.terminal_total_rows <= 2 {
             page_range_begin + 1
         } else {
             min(
                 self.total_content_lines(),
-                page_range_begin + terminal_total_rows as usize - 2,
+                page_range_begin + self.page_height(),
             )
         };
         Range::new(page_range_begin, page_range_end)
     }
 
+    /// Goes to the next day. Past today, only the days with a log file are visited,
+    /// which can exist if the clock was wrong when writing.
     pub fn next_day(&mut self) {
-        if self.date == get_today_date() {
-            let err_msg = "This is already today's log";
+        let log_dates = get_all_log_dates(&self.log_dir_path).unwrap_or_default();
+        let Some(next_date) = next_date(self.date, get_today_date(), &log_dates) else {
+            let err_msg = if self.date == get_today_date() {
+                "This is already today's log"
+            } else {
+                "There is no later log"
+            };
             self.show_error_message(err_msg);
             return;
-        }
-        self.date = self
-            .date
-            .checked_add_days(Days::new(1))
-            .expect("Date out of range");
-
-        self.update_log_items();
-        self.begin_char_index = 0;
+        };
+        self.open_date(next_date);
     }
 
     pub fn prev_day(&mut self) {
-        self.date = self
+        let prev_date = self
             .date
             .checked_sub_days(Days::new(1))
             .expect("Date out of range");
+        self.open_date(prev_date);
+    }
 
+    /// Shows the log items of `date` from the top, or from the first search result
+    /// if there is a search pattern. A day without lines has nothing to search.
+    fn open_date(&mut self, date: NaiveDate) {
+        self.date = date;
         self.update_log_items();
         self.begin_char_index = 0;
+        if self.search_pattern.is_some() && !self.colored_lines.is_empty() {
+            self.search_next(false);
+        }
     }
 
     pub fn next_line(&mut self) {
@@ -128,6 +257,21 @@ impl SingleDatePager {
         self.set_begin_line_index(page_range.begin + 1);
     }
 
+    /// Scrolls by a page, or by half a page if `half` is true, keeping `display.scroll_overlap`
+    /// lines of the current page.
+    fn scroll_page(&mut self, forward: bool, half: bool) {
+        let page_height = self.page_height();
+        let distance = scroll_distance(page_height, self.display_config.scroll_overlap, half);
+        let target = scroll_target(
+            self.page_range().begin,
+            distance,
+            forward,
+            self.total_content_lines(),
+            page_height,
+        );
+        self.set_begin_line_index(target);
+    }
+
     pub fn prev_line(&mut self) {
         let page_range_begin = self.page_range().begin;
         if page_range_begin == 0 {
@@ -146,17 +290,61 @@ impl SingleDatePager {
         self.set_begin_line_index(original_page_range.begin + diff);
     }
 
+    /// Moves the page to the end, e.g. to start at the newest log message of today.
+    /// Call it after the setters that change the lines, such as `set_display_config`.
+    pub fn start_at_end(&mut self) {
+        self.goto_page_end();
+    }
+
+    /// Shows `content` instead of the log file, e.g. to page a log file from stdin.
+    pub fn set_piped_content(&mut self, content: String) {
+        self.piped_content = Some(content);
+        self.update_log_items();
+    }
+
+    /// Shows an error and returns true if the piped content is shown, which has no log file
+    /// to edit or navigate from.
+    fn reject_if_piped(&mut self) -> bool {
+        if self.piped_content.is_some() {
+            self.show_error_message("Not available for the content from stdin");
+        }
+        self.piped_content.is_some()
+    }
+
     fn update_log_items(&mut self) {
         let file_path = construct_log_file_path(&self.log_dir_path, &self.date);
 
-        let file_content = std::fs::read_to_string(&file_path).unwrap_or_else(|_err| {
-            if self.verbose {
-                self.show_error_message(&format!("'{}' doesn't exist", file_path.display()));
-            }
-            String::new()
-        });
+        let file_content = match &self.piped_content {
+            Some(content) => content.clone(),
+            None => match read_to_string_lossy(&file_path) {
+                Ok((content, is_utf8)) => {
+                    if !is_utf8 {
+                        self.show_error_message(&format!(
+                            "'{}' isn't valid UTF-8, the invalid bytes are shown as \u{FFFD}",
+                            file_path.display()
+                        ));
+                    }
+                    content
+                }
+                Err(err) => {
+                    if err.kind() == std::io::ErrorKind::PermissionDenied {
+                        self.show_error_message(&format!(
+                            "Permission denied: '{}'",
+                            file_path.display()
+                        ));
+                    } else if self.verbose {
+                        self.show_error_message(&format!(
+                            "'{}' doesn't exist",
+                            file_path.display()
+                        ));
+                    }
+                    String::new()
+                }
+            },
+        };
 
         self.log_item_list = LogItemList::from_str(&file_content).expect("Invalid log file");
+        self.raw_content = file_content;
         self.update_colored_lines();
         // let _ = self
         //     .pager
@@ -171,28 +359,50 @@ impl SingleDatePager {
 
         let range = self.page_range();
 
-        let colored_lines = &self.colored_lines;
+        let colored_lines = &self.colored_lines[range.begin..range.end];
 
-        for i in range.begin..range.end {
-            if i != range.begin {
+        for (i, line) in colored_lines.iter().enumerate() {
+            if i != 0 {
                 queue!(stdout, cursor::MoveToNextLine(1))?;
             }
-            queue!(stdout, Print(&colored_lines[i]))?;
+            let width = self.terminal_total_cols as usize;
+            let line = slice_colored_line(line, self.col_offset, width);
+            if i == 0 && self.display_config.highlight_current_line {
+                queue!(
+                    stdout,
+                    Print(set_line_background(&line, width, CURRENT_LINE_BACKGROUND))
+                )?;
+            } else {
+                queue!(stdout, Print(line))?;
+            }
         }
 
         Ok(())
     }
 
+    /// Expands the prompt format for the date line.
+    fn prompt(&self) -> String {
+        expand_template(&self.prompt_format, |name| match name {
+            "date" => Some(self.date.to_string()),
+            "weekday" => Some(weekday_name(self.date, self.display_config.locale)),
+            "count" => Some(self.log_item_list.len().to_string()),
+            "percent" => {
+                let percent = (self.page_range().end * 100)
+                    .checked_div(self.total_content_lines())
+                    .unwrap_or(100);
+                Some(format!("{}%", percent))
+            }
+            _ => None,
+        })
+    }
+
     fn print_colored_date(&self, stdout: &mut Stdout) -> Result<(), std::io::Error> {
         let terminal_total_rows = self.terminal_total_rows;
         if terminal_total_rows <= 1 {
             return Ok(());
         }
-        let content_style = ContentStyle::new().dark_grey();
-        let styled_content = StyledContent::new(
-            content_style,
-            format!("{} {}", self.date, self.date.weekday()),
-        );
+        let content_style = ContentStyle::new().with(self.theme.date_line);
+        let styled_content = StyledContent::new(content_style, self.prompt());
         let row_index = if terminal_total_rows == 2 {
             1
         } else {
@@ -207,17 +417,46 @@ impl SingleDatePager {
         Ok(())
     }
 
+    /// Returns the bottom message, or the key hints if there is no message in the view mode.
+    fn status_line(&self) -> StyledContent<String> {
+        let is_idle = self.mode == PagerMode::View && self.bottom_message.content().is_empty();
+        if is_idle && self.display_config.show_hints {
+            // Leave a space before the clock
+            let width = if self.display_config.clock {
+                self.terminal_total_cols.saturating_sub(CLOCK_WIDTH + 1)
+            } else {
+                self.terminal_total_cols
+            };
+            let hints: String = KEY_HINTS.chars().take(width as usize).collect();
+            StyledContent::new(ContentStyle::new().dark_grey(), hints)
+        } else {
+            self.bottom_message.clone()
+        }
+    }
+
     fn print_colored_message(&self, stdout: &mut Stdout) -> Result<(), std::io::Error> {
         let terminal_total_rows = self.terminal_total_rows;
         crossterm::queue!(
             stdout,
             cursor::MoveTo(0, terminal_total_rows - 1),
-            PrintStyledContent(self.bottom_message.clone())
+            PrintStyledContent(self.status_line())
         )?;
 
         Ok(())
     }
 
+    /// Prints the clock of `display.clock` in the view mode. It's hidden while a message
+    /// is shown, which it could cover.
+    fn print_clock(&self, stdout: &mut Stdout) -> Result<(), std::io::Error> {
+        if self.display_config.clock
+            && self.mode == PagerMode::View
+            && self.bottom_message.content().is_empty()
+        {
+            queue_clock(stdout, self.terminal_total_cols, self.terminal_total_rows)?;
+        }
+        Ok(())
+    }
+
     fn print_command(&self, stdout: &mut Stdout) -> Result<(), std::io::Error> {
         let terminal_total_rows = self.terminal_total_rows;
         crossterm::queue!(
@@ -238,6 +477,10 @@ impl SingleDatePager {
             Print('/'),
             Print(&self.search_pattern_input)
         )?;
+        if self.search_pattern_input.is_empty() {
+            let hint = format!("(prefix with `{}` to search the dates)", DATE_SEARCH_PREFIX);
+            crossterm::queue!(stdout, Print(hint.dark_grey()))?;
+        }
 
         Ok(())
     }
@@ -253,6 +496,7 @@ impl SingleDatePager {
         self.print_colored_file_content(&mut stdout)?;
         self.print_colored_date(&mut stdout)?;
         self.print_colored_message(&mut stdout)?;
+        self.print_clock(&mut stdout)?;
         match self.mode {
             PagerMode::Command => {
                 self.print_command(&mut stdout)?;
@@ -260,6 +504,13 @@ impl SingleDatePager {
             PagerMode::Search => {
                 self.print_search_pattern_input(&mut stdout)?;
             }
+            PagerMode::GotoDate => {
+                queue_goto_date_input(
+                    &mut stdout,
+                    self.terminal_total_rows,
+                    &self.goto_date_input,
+                )?;
+            }
             _ => {}
         }
 
@@ -268,24 +519,92 @@ impl SingleDatePager {
     }
 
     fn show_error_message(&mut self, msg: &str) {
-        let content_style = ContentStyle::new().white().on_red();
+        let content_style = ContentStyle::new().white().on(self.theme.error);
         self.bottom_message = StyledContent::new(content_style, msg.to_owned());
     }
 
+    fn show_message(&mut self, msg: &str) {
+        self.bottom_message = StyledContent::new(ContentStyle::new(), msg.to_owned());
+    }
+
     pub fn clear_error_message(&mut self) {
         self.bottom_message = StyledContent::new(ContentStyle::new(), String::new());
     }
 
-    fn highlight_log_item<'h>(&self, log_item: &'h LogItem) -> String {
-        let date_str = format!("[{}]", log_item.date_time().format("%Y-%m-%d %H:%M"));
-        let content: &str = log_item.content();
-        let new_content: Cow<'h, str>;
-        if let Some(regex) = &self.search_pattern {
-            new_content = mark_search_result(regex, content);
+    /// Marks the results of `search_pattern` and the highlights in `content`, and styles
+    /// the syntax of the rest if `syntax` is true.
+    fn mark_content<'h>(
+        &self,
+        content: &'h str,
+        search_pattern: Option<&regex::Regex>,
+        syntax: bool,
+    ) -> Cow<'h, str> {
+        if !self.highlights.is_empty() {
+            let mark_rest = |s: &str| {
+                mark_highlights(&self.highlights, s, |s| {
+                    if syntax {
+                        mark_syntax(s)
+                    } else {
+                        s.to_owned()
+                    }
+                })
+            };
+            Cow::Owned(mark_search_result_and_rest(
+                search_pattern,
+                content,
+                self.theme.search_match,
+                mark_rest,
+            ))
+        } else if syntax {
+            Cow::Owned(mark_search_result_and_syntax(
+                search_pattern,
+                content,
+                self.theme.search_match,
+            ))
+        } else if let Some(regex) = search_pattern {
+            mark_search_result(regex, content, self.theme.search_match)
         } else {
-            new_content = Cow::Borrowed(content);
+            Cow::Borrowed(content)
+        }
+    }
+
+    /// Colors the log item. If `show_timestamp` is false, the timestamp is replaced with
+    /// spaces, unless the dates are searched.
+    fn highlight_log_item(&self, log_item: &LogItem, show_timestamp: bool) -> String {
+        let date_text = log_item.date_time().format("%Y-%m-%d %H:%M").to_string();
+        // The dates are searched in the date time format, so it's kept while searching them
+        let timestamp_text = if self.display_config.relative_time {
+            relative_time_text(*log_item.date_time())
+        } else {
+            date_text.clone()
+        };
+        let date_str = match &self.search_pattern {
+            Some(regex) if self.search_field == SearchField::Date => {
+                format!(
+                    "[{}]",
+                    mark_search_result(regex, &date_text, self.theme.search_match)
+                )
+            }
+            _ if !show_timestamp => " ".repeat(timestamp_text.len() + 2),
+            _ => format!("[{}]", timestamp_text)
+                .with(self.theme.timestamp)
+                .to_string(),
+        };
+        // The content is only marked if it's searched
+        let content_search_pattern = self
+            .search_pattern
+            .as_ref()
+            .filter(|_| self.search_field == SearchField::Content);
+        let new_content = self.mark_content(
+            log_item.content(),
+            content_search_pattern,
+            self.display_config.highlight_syntax,
+        );
+        if self.display_config.show_entry_ids {
+            format!("{} {} {}", date_str, log_item.id().dark_grey(), new_content)
+        } else {
+            format!("{} {}", date_str, new_content)
         }
-        format!("{} {}", date_str.green(), new_content)
     }
 
     /// Splits the log content into lines that fit within the terminal width,
@@ -298,32 +617,261 @@ impl SingleDatePager {
         let terminal_total_cols = self.terminal_total_cols as usize;
 
         self.colored_lines.clear();
-        for item in self.log_item_list.iter() {
-            for line in self.highlight_log_item(item).lines() {
-                self.colored_lines.extend(
-                    textwrap::wrap(line, terminal_total_cols)
-                        .iter()
-                        .map(|x| x.to_string()),
-                );
+        self.line_item_indices.clear();
+        if self.show_raw_content {
+            for line in self.raw_content.lines() {
+                let line = self.mark_content(line, self.search_pattern.as_ref(), false);
+                self.colored_lines
+                    .extend(wrap_line(&line, terminal_total_cols, self.wrap));
+            }
+            // The raw lines don't belong to any parsed log item
+            self.line_item_indices
+                .resize(self.colored_lines.len(), None);
+            return;
+        }
+        let mut added_line_flags = self.added_line_flags.iter().flatten();
+        let mut prev_date_time = None;
+        for (item_index, item) in self.log_item_list.iter().enumerate() {
+            let show_timestamp =
+                !self.display_config.group_same_minute || prev_date_time != Some(item.date_time());
+            let highlighted = self.highlight_log_item(item, show_timestamp);
+            if self.display_config.hide_empty_entries && item.is_content_empty() {
+                // Skip the flags of the hidden lines
+                added_line_flags.nth(highlighted.lines().count().saturating_sub(1));
+                continue;
+            }
+            prev_date_time = Some(item.date_time());
+            for line in highlighted.lines() {
+                if added_line_flags.next() == Some(&true) {
+                    // Leave room for the marker
+                    let width = terminal_total_cols.saturating_sub(1).max(1);
+                    self.colored_lines.extend(
+                        wrap_line(line, width, self.wrap)
+                            .iter()
+                            .map(|x| format!("{}{}", "▎".green(), x)),
+                    );
+                } else {
+                    self.colored_lines
+                        .extend(wrap_line(line, terminal_total_cols, self.wrap));
+                }
+            }
+            self.line_item_indices
+                .resize(self.colored_lines.len(), Some(item_index));
+        }
+    }
+
+    /// Removes the markers of the lines added by the last edit.
+    fn clear_added_line_marks(&mut self) {
+        if self.added_line_flags.take().is_some() {
+            self.refresh_colored_lines();
+        }
+    }
+
+    /// Highlights the matches of `pattern` in the next color, keeping the position of the page.
+    fn add_highlight(&mut self, pattern: &str) {
+        match regex::Regex::new(pattern) {
+            Ok(regex) => {
+                let color = HIGHLIGHT_COLORS[self.highlights.len() % HIGHLIGHT_COLORS.len()];
+                self.highlights.push((regex, color));
+                self.refresh_colored_lines();
             }
+            Err(_) => self.show_error_message(&format!("invalid pattern: {}", pattern)),
+        }
+    }
+
+    /// Removes the highlights added by `add_highlight`, keeping the position of the page.
+    fn clear_highlights(&mut self) {
+        if !self.highlights.is_empty() {
+            self.highlights.clear();
+            self.refresh_colored_lines();
         }
     }
 
+    /// Updates the colored lines, keeping the position of the page.
+    fn refresh_colored_lines(&mut self) {
+        let begin_line_index = self.page_range().begin;
+        self.update_colored_lines();
+        self.set_begin_line_index(begin_line_index);
+    }
+
+    /// Switches between the parsed log items and the raw content of the log file.
+    fn toggle_raw_content(&mut self) {
+        self.show_raw_content = !self.show_raw_content;
+        self.update_colored_lines();
+        self.goto_page_begin();
+    }
+
+    /// The largest `col_offset`, at which the end of the longest line is at the right edge.
+    fn max_col_offset(&self) -> usize {
+        let max_line_width = self
+            .colored_lines
+            .iter()
+            .map(|line| textwrap::core::display_width(line))
+            .max()
+            .unwrap_or(0);
+        max_line_width.saturating_sub(self.terminal_total_cols as usize)
+    }
+
+    /// The number of columns scrolled by a horizontal scroll, which is half the page width.
+    fn horizontal_scroll_step(&self) -> usize {
+        (self.terminal_total_cols as usize / 2).max(1)
+    }
+
+    fn set_wrap(&mut self, wrap: bool) {
+        self.wrap = wrap;
+        self.update_colored_lines();
+    }
+
+    fn toggle_wrap(&mut self) {
+        // The page stays at the same text, since `begin_char_index` ignores the line breaks
+        self.set_wrap(!self.wrap);
+        self.col_offset = 0;
+    }
+
+    /// Switches between the date times and the relative times of the log items.
+    /// The page stays at the log item of the top line, since the length of the lines changes.
+    fn toggle_relative_time(&mut self) {
+        let top_item_index = self.top_line_item_index();
+        self.display_config.relative_time = !self.display_config.relative_time;
+        self.update_colored_lines();
+        if let Some(line_index) = top_item_index.and_then(|item_index| {
+            self.line_item_indices
+                .iter()
+                .position(|x| *x == Some(item_index))
+        }) {
+            self.set_begin_line_index(line_index);
+        }
+    }
+
+    fn scroll_left(&mut self) {
+        self.col_offset = self
+            .col_offset
+            .saturating_sub(self.horizontal_scroll_step());
+    }
+
+    fn scroll_right(&mut self) {
+        self.col_offset = min(
+            self.col_offset + self.horizontal_scroll_step(),
+            self.max_col_offset(),
+        );
+    }
+
     fn resize(&mut self, columns: u16, rows: u16) {
         self.terminal_total_cols = columns;
         self.terminal_total_rows = rows;
         self.update_colored_lines();
+        self.col_offset = min(self.col_offset, self.max_col_offset());
     }
 
     fn edit(&mut self) -> Result<(), std::io::Error> {
         let log_dir_path = &self.log_dir_path;
         let date = &self.date;
         let file_path = construct_log_file_path(log_dir_path, date);
-        crate::utils::terminal::restore_terminal().expect("Unable to restore the terminal");
-        edit::edit_file(file_path)?;
+        let old_content = self.log_item_list.to_string();
+        crate::utils::terminal::restore_terminal()?;
+        // Don't leave an empty file behind if nothing was written
+        let result = edit_file(&file_path, &self.editor)
+            .and_then(|_| remove_file_if_blank(&file_path).map(|_| ()));
+        // The pager is shown again even if the editor failed
+        if self.display_config.alternate_screen {
+            execute!(stdout(), crossterm::terminal::EnterAlternateScreen)?;
+        }
+        result?;
         self.update_log_items();
-        execute!(stdout(), crossterm::terminal::EnterAlternateScreen)
-            .expect("Unable to enter alternate screen");
+        self.added_line_flags = Some(get_added_line_flags(
+            &old_content,
+            &self.log_item_list.to_string(),
+        ));
+        self.update_colored_lines();
+        Ok(())
+    }
+
+    /// Edits the log file of the current date, showing the error if the editor
+    /// isn't found or fails.
+    fn edit_or_show_error(&mut self) {
+        let result = find_editor(&self.editor)
+            .map_err(|error| error.to_string())
+            .and_then(|_| self.edit().map_err(|error| error.to_string()));
+        if let Err(message) = result {
+            self.show_error_message(&message);
+        }
+    }
+
+    /// The index of the log item of the top line.
+    fn top_line_item_index(&self) -> Option<usize> {
+        self.line_item_indices
+            .get(self.page_range().begin)
+            .copied()
+            .flatten()
+    }
+
+    /// Copies the content of the log item of the top line to the clipboard.
+    fn copy_top_log_item(&mut self) {
+        let Some(item_index) = self.top_line_item_index() else {
+            self.show_error_message("There is no log entry to copy on the top line");
+            return;
+        };
+        let content = self.log_item_list.iter().nth(item_index).unwrap().content();
+        match copy_to_clipboard(&mut self.clipboard, content) {
+            Ok(()) => self.show_message("Copied"),
+            Err(error) => self.show_error_message(&format!("Unable to copy: {}", error)),
+        }
+    }
+
+    /// Asks for the confirmation to delete the log item of the top line.
+    fn request_deletion(&mut self) {
+        match self.top_line_item_index() {
+            Some(item_index) => {
+                self.pending_deletion = Some(item_index);
+                self.show_message("Delete this log entry? (y/N)");
+            }
+            None => self.show_error_message("There is no log entry to delete on the top line"),
+        }
+    }
+
+    /// Deletes the log item at `item_index` and rewrites the log file with the others.
+    /// The log file is read again under the lock, and left unchanged if it has text before
+    /// the first log item or isn't valid UTF-8, which rewriting it would lose, or if the
+    /// log item at `item_index` isn't the shown one anymore.
+    fn delete_log_item(&mut self, item_index: usize) -> Result<(), Error> {
+        let begin_line_index = self.page_range().begin;
+        let file_path = construct_log_file_path(&self.log_dir_path, &self.date);
+        let mut file =
+            open_locked(&file_path).map_err(|error| Error::from_io(error, &file_path))?;
+        let mut content = String::new();
+        file.read_to_string(&mut content)
+            .map_err(|error| Error::from_io(error, &file_path))?;
+        let mut log_item_list =
+            parse_log_content(&content).ok_or_else(|| Error::LeadingText(file_path.clone()))?;
+
+        // The log file may have been changed since it was shown, e.g. by an editor
+        let is_shown_item = match (
+            log_item_list.iter().nth(item_index),
+            self.log_item_list.iter().nth(item_index),
+        ) {
+            (Some(log_item), Some(shown_log_item)) => {
+                log_item.date_time() == shown_log_item.date_time()
+                    && log_item.content() == shown_log_item.content()
+            }
+            _ => false,
+        };
+        if !is_shown_item {
+            drop(file);
+            // The log file is created by `open_locked` if it has been removed
+            remove_file_if_blank(&file_path).map_err(|error| Error::from_io(error, &file_path))?;
+            self.update_log_items();
+            return Err(Error::LogFileChanged(file_path));
+        }
+
+        log_item_list.remove(item_index);
+        overwrite_file(&mut file, &log_item_list.to_string())
+            .map_err(|error| Error::from_io(error, &file_path))?;
+        drop(file);
+        remove_file_if_blank(&file_path).map_err(|error| Error::from_io(error, &file_path))?;
+        self.update_log_items();
+        let last_line_index = self.total_content_lines().saturating_sub(1);
+        self.set_begin_line_index(begin_line_index.min(last_line_index));
+        self.show_message("Deleted the log entry");
         Ok(())
     }
 
@@ -332,30 +880,85 @@ impl SingleDatePager {
     }
 
     fn enter_search_mode(&mut self) {
+        self.search_history.reset();
         self.mode = PagerMode::Search;
     }
 
+    fn enter_goto_date_mode(&mut self) {
+        self.mode = PagerMode::GotoDate;
+    }
+
     fn exit(&mut self) {
         self.is_exit = true;
     }
 
     fn handle_view_event(&mut self, event: ViewEvent) {
+        let is_quit_pending = std::mem::take(&mut self.is_quit_pending);
+        let pending_deletion = self.pending_deletion.take();
         self.clear_error_message();
+        self.clear_added_line_marks();
         match event {
+            ViewEvent::NextDay
+            | ViewEvent::PrevDay
+            | ViewEvent::Edit
+            | ViewEvent::Delete
+            | ViewEvent::EnterGotoDateMode
+                if self.reject_if_piped() => {}
             ViewEvent::NextDay => self.next_day(),
             ViewEvent::PrevDay => self.prev_day(),
             ViewEvent::NextLine => self.next_line(),
+            ViewEvent::NextPage => self.scroll_page(true, false),
+            ViewEvent::PrevPage => self.scroll_page(false, false),
+            ViewEvent::NextHalfPage => self.scroll_page(true, true),
+            ViewEvent::PrevHalfPage => self.scroll_page(false, true),
             ViewEvent::PrevLine => self.prev_line(),
+            ViewEvent::ScrollLeft => self.scroll_left(),
+            ViewEvent::ScrollRight => self.scroll_right(),
+            ViewEvent::ToggleWrap => self.toggle_wrap(),
+            ViewEvent::ToggleRelativeTime => self.toggle_relative_time(),
             ViewEvent::GotoPageBegin => self.goto_page_begin(),
             ViewEvent::GotoPageEnd => self.goto_page_end(),
             ViewEvent::Quit => self.exit(),
-            ViewEvent::Edit => self.edit().expect("Unable to edit the file"),
-            ViewEvent::SearchNext => self.search_next(true),
-            ViewEvent::SearchPrev => self.search_prev(),
+            ViewEvent::QuitPrefix => {
+                if is_quit_pending {
+                    self.exit();
+                } else {
+                    self.is_quit_pending = true;
+                }
+            }
+            ViewEvent::Edit => self.edit_or_show_error(),
+            ViewEvent::ToggleRawContent => self.toggle_raw_content(),
+            ViewEvent::SearchNext => {
+                self.search_next(true);
+            }
+            ViewEvent::SearchPrev => {
+                self.search_prev();
+            }
+            ViewEvent::NextEntry => {
+                if !self.next_entry() {
+                    self.show_error_message("This is already the last entry");
+                }
+            }
+            ViewEvent::PrevEntry => {
+                if !self.prev_entry() {
+                    self.show_error_message("This is already the first entry");
+                }
+            }
             ViewEvent::Resize(columns, rows) => self.resize(columns, rows),
             ViewEvent::EnterCommandMode => self.enter_command_mode(),
             ViewEvent::EnterSearchMode => self.enter_search_mode(),
-            ViewEvent::None => {}
+            ViewEvent::EnterGotoDateMode => self.enter_goto_date_mode(),
+            ViewEvent::Delete => self.request_deletion(),
+            ViewEvent::Confirm | ViewEvent::Copy => match pending_deletion {
+                Some(item_index) => {
+                    if let Err(error) = self.delete_log_item(item_index) {
+                        self.show_error_message(&error.to_string());
+                    }
+                }
+                None if matches!(event, ViewEvent::Copy) => self.copy_top_log_item(),
+                None => {}
+            },
+            ViewEvent::OpenDate | ViewEvent::None => {}
         }
 
         self.print_pager().expect("Unable to print the pager");
@@ -363,21 +966,83 @@ impl SingleDatePager {
 
     fn enter_view_mode(&mut self) {
         self.command_buffer.clear();
+        self.goto_date_input.clear();
         self.mode = PagerMode::View;
     }
 
+    /// Opens the date typed in the goto-date mode, or shows an error if it's invalid.
+    fn confirm_goto_date(&mut self) {
+        let date_str = self.goto_date_input.trim().to_owned();
+        self.enter_view_mode();
+        if date_str.is_empty() {
+            return;
+        }
+        match parse_date_from_str(
+            &date_str,
+            &self.date_input_formats,
+            self.yearless_dates_in_past,
+        ) {
+            Ok(date) => self.open_date(date),
+            Err(_) => self.show_error_message(&format!("invalid date: {}", date_str)),
+        }
+    }
+
+    fn handle_goto_date_event(&mut self, event: GotoDateEvent) {
+        self.clear_error_message();
+        match event {
+            GotoDateEvent::Confirm => self.confirm_goto_date(),
+            GotoDateEvent::Char(c) => self.goto_date_input.push(c),
+            GotoDateEvent::None => {}
+            GotoDateEvent::Cancel => self.enter_view_mode(),
+            GotoDateEvent::Backspace => {
+                if self.goto_date_input.pop().is_none() {
+                    self.enter_view_mode();
+                }
+            }
+            GotoDateEvent::ClearLine => self.goto_date_input.clear(),
+        }
+        self.print_pager().expect("Unable to print the pager");
+    }
+
     fn execute_command(&mut self) {
         let command_str = &self.command_buffer;
         let command = command::Command::from_str(command_str).unwrap();
         match command {
             command::Command::None => {}
-            command::Command::ShowDate => todo!(),
+            command::Command::ShowDate => {
+                self.show_message(&format!(
+                    "{} {}",
+                    self.date,
+                    weekday_name(self.date, self.display_config.locale)
+                ));
+            }
+            command::Command::SetDate(_) | command::Command::Edit(_) if self.reject_if_piped() => {}
             command::Command::SetDate(date_str) => {
-                if let Ok(date) = NaiveDate::from_str(&date_str) {
-                    self.date = date;
-                    self.update_log_items();
+                match parse_date_from_str(
+                    &date_str,
+                    &self.date_input_formats,
+                    self.yearless_dates_in_past,
+                ) {
+                    Ok(date) => self.open_date(date),
+                    Err(_) => self.show_error_message(&format!("invalid date: {}", date_str)),
                 }
             }
+            command::Command::Edit(date_str) => {
+                match parse_date_from_str(
+                    &date_str,
+                    &self.date_input_formats,
+                    self.yearless_dates_in_past,
+                ) {
+                    Ok(date) => {
+                        self.date = date;
+                        self.update_log_items();
+                        self.edit_or_show_error();
+                    }
+                    Err(_) => self.show_error_message(&format!("invalid date: {}", date_str)),
+                }
+            }
+            command::Command::Highlight(pattern) => self.add_highlight(&pattern),
+            command::Command::ClearHighlights => self.clear_highlights(),
         }
 
         self.command_buffer.clear();
@@ -405,10 +1070,10 @@ impl SingleDatePager {
 
     fn confirm_search(&mut self) {
         let search_pattern_input = &mut self.search_pattern_input;
-        self.search_pattern = match regex::Regex::new(search_pattern_input) {
-            Ok(result) => Some(result),
-            Err(_) => None,
-        };
+        let (search_field, pattern) = parse_search_input(search_pattern_input);
+        self.search_field = search_field;
+        self.search_pattern = regex::Regex::new(pattern).ok();
+        self.search_history.push(search_pattern_input);
         search_pattern_input.clear();
         self.update_colored_lines();
         self.enter_view_mode();
@@ -430,18 +1095,37 @@ impl SingleDatePager {
                 }
             }
             SearchEvent::ClearLine => self.search_pattern_input.clear(),
+            SearchEvent::HistoryPrev => {
+                if let Some(pattern) = self.search_history.recall_prev(&self.search_pattern_input) {
+                    self.search_pattern_input = pattern.to_owned();
+                }
+            }
+            SearchEvent::HistoryNext => {
+                if let Some(pattern) = self.search_history.recall_next() {
+                    self.search_pattern_input = pattern.to_owned();
+                }
+            }
         }
         self.print_pager().expect("Unable to print the pager");
     }
 
     pub fn run(&mut self) {
-        enable_raw_mode().expect("Failed to enable raw mode");
-        execute!(stdout(), crossterm::terminal::EnterAlternateScreen)
-            .expect("Unable to enter alternate screen");
+        let _terminal_guard = TerminalGuard::new(self.display_config.alternate_screen)
+            .expect("Unable to prepare the terminal");
         self.print_pager().expect("Print pager");
 
         while !self.is_exit {
-            let crossterm_event = crossterm::event::read().expect("Unable to read the event");
+            let crossterm_event =
+                match read_event(self.display_config.clock).expect("Unable to read the event") {
+                    Some(crossterm_event) => crossterm_event,
+                    None => {
+                        let mut stdout = stdout();
+                        self.print_clock(&mut stdout)
+                            .and_then(|_| stdout.flush())
+                            .expect("Unable to print the clock");
+                        continue;
+                    }
+                };
             match self.mode {
                 PagerMode::View => {
                     let event = ViewEvent::from_crossterm_event(crossterm_event);
@@ -455,27 +1139,46 @@ impl SingleDatePager {
                     let event = SearchEvent::from_crossterm_event(crossterm_event);
                     self.handle_search_event(event);
                 }
+                PagerMode::GotoDate => {
+                    let event = GotoDateEvent::from_crossterm_event(crossterm_event);
+                    self.handle_goto_date_event(event);
+                }
             }
         }
-
-        crate::utils::terminal::restore_terminal().expect("Unable to restore the terminal");
-
-        disable_raw_mode().expect("Unable to diable raw mode");
     }
 }
 
 impl Pager for SingleDatePager {
     fn begin_line_index(&self) -> usize {
-        get_line_index_by_char_index(&self.colored_lines, self.begin_char_index).unwrap()
+        get_line_index_by_char_index(&self.colored_lines, self.begin_char_index).unwrap_or(0)
     }
 
     fn colored_lines(&self) -> &Vec<String> {
         &self.colored_lines
     }
 
+    fn line_item_indices(&self) -> &Vec<Option<usize>> {
+        &self.line_item_indices
+    }
+
     fn set_begin_line_index(&mut self, line_index: usize) {
         self.begin_char_index = get_char_index_by_line_index(&self.colored_lines, line_index);
     }
+
+    fn search_background(&self) -> Color {
+        self.theme.search_match
+    }
+}
+
+/// Returns the day after `date` up to `today`, or the first date in `log_dates` (ascending)
+/// after `date` beyond today.
+fn next_date(date: NaiveDate, today: NaiveDate, log_dates: &[NaiveDate]) -> Option<NaiveDate> {
+    let next_date = date.checked_add_days(Days::new(1))?;
+    if next_date <= today {
+        return Some(next_date);
+    }
+    let index = log_dates.partition_point(|log_date| *log_date <= date);
+    log_dates.get(index).copied()
 }
 
 #[cfg(test)]
@@ -483,13 +1186,22 @@ mod test {
     use std::{path::PathBuf, str::FromStr};
 
     use chrono::NaiveDate;
+    use crossterm::style::{Color, Stylize};
 
     use crate::{
+        config::construct_log_file_path,
         log_item::LogItemList,
-        log_pager::{pager::Pager, search::Search},
+        log_pager::{
+            entry_navigation::EntryNavigation,
+            pager::Pager,
+            pager_mode::PagerMode,
+            search::{Search, SearchField},
+        },
+        theme::Theme,
+        utils::time::get_today_date,
     };
 
-    use super::SingleDatePager;
+    use super::{next_date, SingleDatePager, KEY_HINTS};
 
     #[test]
     fn test_begin_line_index() {
@@ -504,6 +1216,415 @@ mod test {
         assert_eq!(pager.begin_line_index(), 2);
     }
 
+    #[test]
+    fn test_navigate_on_empty_day() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let mut pager = SingleDatePager::new(NaiveDate::default(), log_dir.path().to_path_buf());
+        assert!(pager.colored_lines.is_empty());
+        assert_eq!(pager.begin_line_index(), 0);
+        assert!(!pager.next_entry());
+        assert!(!pager.prev_entry());
+        pager.search_pattern = regex::Regex::new("foo").ok();
+        assert!(!pager.search_next(false));
+        assert!(!pager.search_prev());
+    }
+
+    #[test]
+    fn test_toggle_raw_content() {
+        let mut pager = SingleDatePager::new(NaiveDate::default(), PathBuf::default());
+        let raw_content = "[2025-02-21 20:20]   spaces  kept\nstray line\n";
+        pager.log_item_list = LogItemList::from_str(raw_content).unwrap();
+        pager.raw_content = raw_content.to_owned();
+        pager.resize(80, 10);
+        let parsed_lines = pager.colored_lines.clone();
+
+        pager.toggle_raw_content();
+        assert_eq!(
+            pager.colored_lines,
+            ["[2025-02-21 20:20]   spaces  kept", "stray line"]
+        );
+        assert_eq!(pager.line_item_indices, [None, None]);
+
+        pager.toggle_raw_content();
+        assert_eq!(pager.colored_lines, parsed_lines);
+    }
+
+    #[test]
+    fn test_toggle_relative_time() {
+        let mut pager = SingleDatePager::new(NaiveDate::default(), PathBuf::default());
+        let content =
+            "[2020-01-01 08:00] first\n[2020-01-02 08:00] second\n[2020-01-03 08:00] third\n";
+        pager.log_item_list = LogItemList::from_str(content).unwrap();
+        pager.resize(80, 2);
+        pager.next_line();
+
+        pager.toggle_relative_time();
+        assert!(pager.colored_lines[1].contains("y ago]"));
+        assert_eq!(pager.begin_line_index(), 1);
+
+        // The dates are shown in the date time format while they're searched
+        pager.search_field = SearchField::Date;
+        pager.search_pattern = regex::Regex::new("01-02").ok();
+        pager.update_colored_lines();
+        assert!(pager.colored_lines[0].starts_with("[2020-01-01 08:00]"));
+
+        pager.search_pattern = None;
+        pager.toggle_relative_time();
+        assert!(pager.colored_lines[0].contains("[2020-01-01 08:00]"));
+        assert_eq!(pager.begin_line_index(), 1);
+    }
+
+    #[test]
+    fn test_highlight_command() {
+        let mut pager = SingleDatePager::new(NaiveDate::default(), PathBuf::default());
+        let raw_content = "foo\nbar\nbaz\n";
+        pager.raw_content = raw_content.to_owned();
+        pager.show_raw_content = true;
+        pager.resize(80, 2);
+        pager.next_line();
+
+        pager.command_buffer = "hl fo+".to_owned();
+        pager.execute_command();
+        pager.command_buffer = "hl ba".to_owned();
+        pager.execute_command();
+        assert_eq!(
+            pager.colored_lines,
+            [
+                "foo".yellow().bold().to_string(),
+                format!("{}r", "ba".magenta().bold()),
+                format!("{}z", "ba".magenta().bold()),
+            ]
+        );
+        // The page isn't moved
+        assert_eq!(pager.begin_line_index(), 1);
+
+        pager.command_buffer = "hl (".to_owned();
+        pager.execute_command();
+        assert_eq!(pager.bottom_message.content(), "invalid pattern: (");
+        assert_eq!(pager.highlights.len(), 2);
+
+        pager.command_buffer = "nohl".to_owned();
+        pager.execute_command();
+        assert_eq!(pager.colored_lines, ["foo", "bar", "baz"]);
+        assert_eq!(pager.begin_line_index(), 1);
+    }
+
+    #[test]
+    fn test_date_command() {
+        let mut pager = SingleDatePager::new(NaiveDate::default(), PathBuf::default());
+        pager.command_buffer = "date foo".to_owned();
+        pager.execute_command();
+        assert_eq!(pager.bottom_message.content(), "invalid date: foo");
+        assert_eq!(pager.date, NaiveDate::default());
+
+        pager.command_buffer = "date 2024-05-01".to_owned();
+        pager.execute_command();
+        assert_eq!(pager.date, NaiveDate::from_ymd_opt(2024, 5, 1).unwrap());
+
+        pager.command_buffer = "date".to_owned();
+        pager.execute_command();
+        assert_eq!(pager.bottom_message.content(), "2024-05-01 Wed");
+    }
+
+    #[test]
+    fn test_goto_date() {
+        let mut pager = SingleDatePager::new(NaiveDate::default(), PathBuf::default());
+        pager.enter_goto_date_mode();
+        pager.goto_date_input = "foo".to_owned();
+        pager.confirm_goto_date();
+        assert!(pager.mode == PagerMode::View);
+        assert!(pager.goto_date_input.is_empty());
+        assert_eq!(pager.bottom_message.content(), "invalid date: foo");
+        assert_eq!(pager.date, NaiveDate::default());
+
+        pager.goto_date_input = " 2024-05-01 ".to_owned();
+        pager.confirm_goto_date();
+        assert_eq!(pager.date, NaiveDate::from_ymd_opt(2024, 5, 1).unwrap());
+
+        pager.goto_date_input = "yesterday".to_owned();
+        pager.confirm_goto_date();
+        assert_eq!(pager.date, get_today_date().pred_opt().unwrap());
+
+        // An empty input goes back to the view mode
+        pager.enter_goto_date_mode();
+        pager.confirm_goto_date();
+        assert!(pager.mode == PagerMode::View);
+        assert_eq!(pager.date, get_today_date().pred_opt().unwrap());
+    }
+
+    #[test]
+    fn test_search_persists_across_days() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let first_date = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let second_date = NaiveDate::from_ymd_opt(2025, 3, 2).unwrap();
+        std::fs::write(
+            construct_log_file_path(log_dir.path(), &first_date),
+            "[2025-03-01 08:00] first\n[2025-03-01 09:00] second\n[2025-03-01 10:00] found\n",
+        )
+        .unwrap();
+        std::fs::write(
+            construct_log_file_path(log_dir.path(), &second_date),
+            "[2025-03-02 08:00] first\n[2025-03-02 09:00] found\n",
+        )
+        .unwrap();
+        let mut pager = SingleDatePager::new(second_date, log_dir.path().to_path_buf());
+        pager.display_config.highlight_syntax = false;
+        pager.resize(80, 10);
+        pager.search_pattern = Some(regex::Regex::new("found").unwrap());
+        pager.update_colored_lines();
+
+        pager.prev_day();
+        assert_eq!(pager.date, first_date);
+        assert_eq!(pager.begin_line_index(), 2);
+        assert_ne!(pager.colored_lines[2], "[2025-03-01 10:00] found");
+        assert!(pager.colored_lines[2].contains("found"));
+
+        pager.next_day();
+        assert_eq!(pager.date, second_date);
+        assert_eq!(pager.begin_line_index(), 1);
+
+        // Without a match, the new day is shown from the top
+        pager.search_pattern = Some(regex::Regex::new("missing").unwrap());
+        pager.prev_day();
+        assert_eq!(pager.begin_line_index(), 0);
+
+        // A day without log items is opened with the search kept
+        pager.search_pattern = Some(regex::Regex::new("found").unwrap());
+        pager.prev_day();
+        assert_eq!(pager.date, NaiveDate::from_ymd_opt(2025, 2, 28).unwrap());
+        assert!(pager.colored_lines.is_empty());
+        assert_eq!(pager.begin_line_index(), 0);
+        pager.next_day();
+        assert_eq!(pager.date, first_date);
+        assert_eq!(pager.begin_line_index(), 2);
+    }
+
+    #[test]
+    fn test_next_date() {
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let today = date("2025-03-01");
+        let log_dates = [date("2025-02-27"), date("2025-03-05"), date("2025-03-09")];
+
+        assert_eq!(
+            next_date(date("2025-02-27"), today, &log_dates),
+            Some(date("2025-02-28"))
+        );
+        assert_eq!(
+            next_date(date("2025-02-28"), today, &log_dates),
+            Some(today)
+        );
+        assert_eq!(
+            next_date(today, today, &log_dates),
+            Some(date("2025-03-05"))
+        );
+        assert_eq!(
+            next_date(date("2025-03-05"), today, &log_dates),
+            Some(date("2025-03-09"))
+        );
+        assert_eq!(next_date(date("2025-03-09"), today, &log_dates), None);
+        assert_eq!(next_date(today, today, &[]), None);
+    }
+
+    #[test]
+    fn test_delete_log_item() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 2, 21).unwrap();
+        let file_path = construct_log_file_path(log_dir.path(), &date);
+        std::fs::write(
+            &file_path,
+            "[2025-02-21 08:00] first\n[2025-02-21 09:00] second\n",
+        )
+        .unwrap();
+        let mut pager = SingleDatePager::new(date, log_dir.path().to_path_buf());
+        pager.resize(80, 10);
+
+        assert_eq!(pager.top_line_item_index(), Some(0));
+        pager.request_deletion();
+        assert_eq!(pager.pending_deletion, Some(0));
+        pager.delete_log_item(0).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&file_path).unwrap(),
+            "[2025-02-21 09:00] second\n"
+        );
+        assert_eq!(pager.log_item_list.len(), 1);
+
+        pager.delete_log_item(0).unwrap();
+        assert!(!file_path.exists());
+
+        // The text before the first log item isn't lost
+        let content = "notes\n[2025-02-21 08:00] first\n";
+        std::fs::write(&file_path, content).unwrap();
+        pager.update_log_items();
+        assert!(matches!(
+            pager.delete_log_item(0),
+            Err(crate::error::Error::LeadingText(_))
+        ));
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), content);
+
+        // The log file has changed since it was shown
+        let content = "[2025-02-21 08:00] first\n[2025-02-21 09:00] second\n";
+        std::fs::write(&file_path, content).unwrap();
+        pager.update_log_items();
+        std::fs::write(&file_path, "[2025-02-21 09:00] second\n").unwrap();
+        assert!(matches!(
+            pager.delete_log_item(0),
+            Err(crate::error::Error::LogFileChanged(_))
+        ));
+        assert_eq!(
+            std::fs::read_to_string(&file_path).unwrap(),
+            "[2025-02-21 09:00] second\n"
+        );
+        // The log items are shown again, and the index is out of range
+        assert_eq!(pager.log_item_list.len(), 1);
+        assert!(matches!(
+            pager.delete_log_item(1),
+            Err(crate::error::Error::LogFileChanged(_))
+        ));
+        std::fs::remove_file(&file_path).unwrap();
+        assert!(matches!(
+            pager.delete_log_item(0),
+            Err(crate::error::Error::LogFileChanged(_))
+        ));
+        assert!(!file_path.exists());
+        pager.update_log_items();
+
+        // Nothing to delete
+        pager.pending_deletion = None;
+        pager.request_deletion();
+        assert_eq!(pager.pending_deletion, None);
+        pager.copy_top_log_item();
+        assert_eq!(
+            pager.bottom_message.content(),
+            "There is no log entry to copy on the top line"
+        );
+    }
+
+    #[test]
+    fn test_status_line() {
+        let mut pager = SingleDatePager::new(NaiveDate::default(), PathBuf::default());
+        pager.resize(80, 10);
+        assert_eq!(pager.status_line().content(), KEY_HINTS);
+
+        pager.show_error_message("error");
+        assert_eq!(pager.status_line().content(), "error");
+
+        pager.clear_error_message();
+        pager.resize(6, 10);
+        assert_eq!(pager.status_line().content(), "q quit");
+
+        // The hints leave room for the clock
+        pager.display_config.clock = true;
+        pager.resize(12, 10);
+        assert_eq!(pager.status_line().content(), "q quit");
+
+        pager.display_config.show_hints = false;
+        assert_eq!(pager.status_line().content(), "");
+    }
+
+    #[test]
+    fn test_invalid_utf8_log_file() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 5, 6).unwrap();
+        let file_path = construct_log_file_path(log_dir.path(), &date);
+        std::fs::write(&file_path, b"[2024-05-06 12:00] caf\xe9 au lait\n").unwrap();
+
+        let pager = SingleDatePager::new(date, log_dir.path().to_path_buf());
+        assert_eq!(
+            pager.log_item_list.iter().next().unwrap().content(),
+            "caf\u{FFFD} au lait"
+        );
+        assert!(pager.bottom_message.content().contains("isn't valid UTF-8"));
+    }
+
+    #[test]
+    fn test_piped_content() {
+        let date = NaiveDate::from_ymd_opt(2025, 2, 21).unwrap();
+        let mut pager = SingleDatePager::new(date, PathBuf::default());
+        pager.set_piped_content("[2024-01-01 08:00] piped\n".to_owned());
+        assert_eq!(
+            pager.log_item_list.iter().next().unwrap().content(),
+            "piped"
+        );
+
+        assert!(pager.reject_if_piped());
+        assert_eq!(
+            pager.bottom_message.content(),
+            "Not available for the content from stdin"
+        );
+        pager.command_buffer = "date 2024-05-01".to_owned();
+        pager.execute_command();
+        assert_eq!(pager.date, date);
+    }
+
+    #[test]
+    fn test_scroll_page() {
+        let mut pager = SingleDatePager::new(NaiveDate::default(), PathBuf::default());
+        let content: String = (0..50)
+            .map(|i| format!("[2025-02-21 20:{:02}] line {}\n", i, i))
+            .collect();
+        pager.log_item_list = LogItemList::from_str(&content).unwrap();
+        pager.resize(80, 12);
+        pager.display_config.scroll_overlap = 2;
+
+        pager.scroll_page(true, false);
+        assert_eq!(pager.begin_line_index(), 8);
+        pager.scroll_page(true, true);
+        assert_eq!(pager.begin_line_index(), 11);
+        pager.scroll_page(false, false);
+        assert_eq!(pager.begin_line_index(), 3);
+        pager.goto_page_end();
+        pager.scroll_page(true, false);
+        assert_eq!(pager.begin_line_index(), 40);
+    }
+
+    #[test]
+    fn test_start_at_end() {
+        let mut pager = SingleDatePager::new(NaiveDate::default(), PathBuf::default());
+        let content: String = (0..50)
+            .map(|i| format!("[2025-02-21 20:{:02}] line {}\n", i, i))
+            .collect();
+        pager.resize(80, 12);
+        pager.set_piped_content(content);
+        assert_eq!(pager.begin_line_index(), 0);
+
+        pager.start_at_end();
+        assert_eq!(pager.page_range().end, pager.total_content_lines());
+        assert!(pager.colored_lines[pager.page_range().end - 1].contains("line 49"));
+    }
+
+    #[test]
+    fn test_toggle_wrap() {
+        let mut pager = SingleDatePager::new(NaiveDate::default(), PathBuf::default());
+        pager.log_item_list =
+            LogItemList::from_str("[2025-02-21 20:20] a long line to wrap\nshort").unwrap();
+        pager.resize(10, 5);
+        assert!(pager.colored_lines.len() > 2);
+
+        pager.toggle_wrap();
+        assert_eq!(pager.colored_lines.len(), 2);
+        pager.toggle_wrap();
+        assert!(pager.colored_lines.len() > 2);
+    }
+
+    #[test]
+    fn test_horizontal_scroll() {
+        let mut pager = SingleDatePager::new(NaiveDate::default(), PathBuf::default());
+        pager.resize(10, 5);
+        pager.colored_lines = vec!["a".repeat(23), "b".repeat(3)];
+        pager.scroll_right();
+        assert_eq!(pager.col_offset, 5);
+        pager.scroll_right();
+        assert_eq!(pager.col_offset, 10);
+        // The end of the longest line is at the right edge
+        pager.scroll_right();
+        assert_eq!(pager.col_offset, 13);
+        pager.scroll_left();
+        assert_eq!(pager.col_offset, 8);
+        pager.scroll_left();
+        pager.scroll_left();
+        assert_eq!(pager.col_offset, 0);
+    }
+
     #[test]
     fn test_search_next() {
         let mut pager = SingleDatePager::new(NaiveDate::default(), PathBuf::default());
@@ -565,6 +1686,30 @@ Java is faster than Python
         assert_eq!(pager.begin_line_index(), 4);
     }
 
+    #[test]
+    fn test_search_with_search_match_color() {
+        let mut pager = SingleDatePager::new(NaiveDate::default(), PathBuf::default());
+        pager.set_theme(Theme {
+            search_match: Color::Blue,
+            ..Theme::default()
+        });
+        pager.log_item_list =
+            LogItemList::from_str("[2025-02-21 13:50] one\ntwo\nthree\nfour").unwrap();
+        pager.resize(80, 20);
+        pager.search_pattern = Some(regex::Regex::new("t").unwrap());
+        // A highlight isn't a search result
+        pager.highlights = vec![(regex::Regex::new("one").unwrap(), Color::White)];
+        pager.update_colored_lines();
+        assert!(pager.search_next(false));
+        assert_eq!(pager.begin_line_index(), 1);
+        assert!(pager.search_next(true));
+        assert_eq!(pager.begin_line_index(), 2);
+        assert!(!pager.search_next(true));
+        assert!(pager.search_prev());
+        assert_eq!(pager.begin_line_index(), 1);
+        assert!(!pager.search_prev());
+    }
+
     // mod resize {
     //     struct TestConfig {
     //         log_dir: PathBuf,
@@ -673,4 +1818,60 @@ Java is faster than Python
     //         }
     //     }
     // }
+
+    #[test]
+    fn test_hide_empty_entries() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let log_file_path = log_dir.path().join("2024-05-12.log");
+        std::fs::write(
+            &log_file_path,
+            "[2024-05-12 09:00] first\n[2024-05-12 10:00]\n[2024-05-12 11:00] third\n",
+        )
+        .unwrap();
+
+        let mut pager = SingleDatePager::new(NaiveDate::default(), PathBuf::default());
+        pager.log_item_list = LogItemList::from_log_file(&log_file_path).unwrap();
+        pager.display_config.highlight_syntax = false;
+        pager.resize(80, 24);
+        pager.update_colored_lines();
+        assert_eq!(pager.colored_lines.len(), 3);
+        assert!(pager
+            .log_item_list
+            .iter()
+            .nth(1)
+            .unwrap()
+            .is_content_empty());
+
+        pager.display_config.hide_empty_entries = true;
+        pager.update_colored_lines();
+        assert_eq!(pager.colored_lines.len(), 2);
+        assert!(pager.colored_lines[1].contains("third"));
+        assert_eq!(pager.line_item_indices, [Some(0), Some(2)]);
+
+        let mut log_item_list = LogItemList::from_log_file(&log_file_path).unwrap();
+        assert_eq!(log_item_list.remove_empty_content(), 1);
+        assert_eq!(log_item_list.len(), 2);
+    }
+
+    #[test]
+    fn test_group_same_minute() {
+        let mut pager = SingleDatePager::new(NaiveDate::default(), PathBuf::default());
+        pager.log_item_list = LogItemList::from_str(
+            "[2024-05-12 09:00] first\n[2024-05-12 09:00] second\nline\n[2024-05-12 09:01] third\n",
+        )
+        .unwrap();
+        pager.display_config.highlight_syntax = false;
+        pager.display_config.group_same_minute = true;
+        pager.resize(80, 24);
+        assert!(pager.colored_lines[0].contains("[2024-05-12 09:00]"));
+        assert_eq!(pager.colored_lines[1], format!("{} second", " ".repeat(18)));
+        assert_eq!(pager.colored_lines[2], "line");
+        assert!(pager.colored_lines[3].contains("[2024-05-12 09:01]"));
+
+        // The timestamps are shown when they are searched
+        pager.search_pattern = Some(regex::Regex::new("09:00").unwrap());
+        pager.search_field = SearchField::Date;
+        pager.update_colored_lines();
+        assert!(pager.colored_lines[1].contains("09:00"));
+    }
 }
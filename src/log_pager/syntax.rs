@@ -0,0 +1,72 @@
+use std::sync::LazyLock;
+
+use crossterm::style::{Color, Stylize};
+use regex::Regex;
+
+use super::search::mark_search_result_and_rest;
+
+static SYNTAX_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"https?://\S+|\B[#@]\w+").expect("Invalid regex"));
+
+/// Underlines the URLs and colors the `#tags` and `@mentions` in `s`.
+pub fn mark_syntax(s: &str) -> String {
+    SYNTAX_REGEX
+        .replace_all(s, |caps: &regex::Captures| {
+            let matched_text = &caps[0];
+            if matched_text.starts_with('#') || matched_text.starts_with('@') {
+                matched_text.cyan().to_string()
+            } else {
+                matched_text.underlined().to_string()
+            }
+        })
+        .into_owned()
+}
+
+/// Marks the search results of `search_pattern` in `s` with the `search_match_background`, and styles the syntax
+/// of the text between the search results.
+pub fn mark_search_result_and_syntax(
+    search_pattern: Option<&Regex>,
+    s: &str,
+    search_match_background: Color,
+) -> String {
+    mark_search_result_and_rest(search_pattern, s, search_match_background, mark_syntax)
+}
+
+#[cfg(test)]
+mod test {
+    use crossterm::style::{Color, Stylize};
+    use regex::Regex;
+
+    use super::{mark_search_result_and_syntax, mark_syntax};
+
+    #[test]
+    fn test_mark_syntax() {
+        assert_eq!(
+            mark_syntax("see https://example.com #rust @me a#b # title"),
+            format!(
+                "see {} {} {} a#b # title",
+                "https://example.com".underlined(),
+                "#rust".cyan(),
+                "@me".cyan()
+            )
+        );
+    }
+
+    #[test]
+    fn test_mark_search_result_and_syntax() {
+        let regex = Regex::new("rust").unwrap();
+        assert_eq!(
+            mark_search_result_and_syntax(Some(&regex), "#rust and rust #go", Color::White),
+            format!(
+                "#{} and {} {}",
+                "rust".black().on_white(),
+                "rust".black().on_white(),
+                "#go".cyan()
+            )
+        );
+        assert_eq!(
+            mark_search_result_and_syntax(None, "#go", Color::White),
+            "#go".cyan().to_string()
+        );
+    }
+}
@@ -1,3 +1,24 @@
+use std::io::Write;
+use std::time::Duration;
+
+use chrono::{Local, NaiveDateTime};
+use crossterm::cursor::MoveTo;
+use crossterm::event::Event;
+use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, Stylize};
+
+use crate::utils::time::{date_time_now, format_time_ago};
+
+/// How often the pagers redraw the clock of `display.clock`.
+pub const CLOCK_REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+/// The number of columns taken by the clock at the bottom right.
+pub const CLOCK_WIDTH: u16 = 5;
+
+/// Returns the time since `date_time` shown by `display.relative_time`, e.g. `  2h ago`.
+/// It's padded to the width of `364d ago`, so that the contents of the log items line up.
+pub fn relative_time_text(date_time: NaiveDateTime) -> String {
+    format!("{:>8}", format_time_ago(date_time, date_time_now()))
+}
+
 /// Compute the index in `lines` of the first character in `line` at `line_index`.
 /// # Example
 /// ```rust
@@ -28,11 +49,141 @@ pub fn get_line_index_by_char_index(lines: &[String], char_index: usize) -> Opti
     None
 }
 
+/// Compares the lines of `old` and `new`, and returns whether each line of `new`
+/// isn't present in `old`. Repeated lines are matched one by one.
+pub fn get_added_line_flags(old: &str, new: &str) -> Vec<bool> {
+    let mut old_lines: Vec<&str> = old.lines().collect();
+    new.lines()
+        .map(
+            |line| match old_lines.iter().position(|old_line| *old_line == line) {
+                Some(index) => {
+                    old_lines.swap_remove(index);
+                    false
+                }
+                None => true,
+            },
+        )
+        .collect()
+}
+
+/// Splits `line` into lines that fit within `width`, or keeps it as is if `wrap` is false.
+pub fn wrap_line(line: &str, width: usize, wrap: bool) -> Vec<String> {
+    if wrap {
+        textwrap::wrap(line, width)
+            .iter()
+            .map(|x| x.to_string())
+            .collect()
+    } else {
+        vec![line.to_owned()]
+    }
+}
+
+/// Returns the part of `line` from the display column `col_offset` that fits in `width` columns.
+/// The ANSI escape sequences are kept, so that the visible part keeps its styles.
+pub fn slice_colored_line(line: &str, col_offset: usize, width: usize) -> String {
+    let mut ret = String::new();
+    let mut col: usize = 0;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            ret.push(c);
+            // A CSI sequence ends with a character in the range '@'..='~'
+            for c in chars.by_ref() {
+                ret.push(c);
+                if c != '[' && ('@'..='~').contains(&c) {
+                    break;
+                }
+            }
+            continue;
+        }
+        let char_width = textwrap::core::display_width(c.encode_utf8(&mut [0; 4]));
+        if col >= col_offset && col + char_width <= col_offset + width {
+            ret.push(c);
+        }
+        col += char_width;
+    }
+    ret
+}
+
+/// The background of the current line if `display.highlight_current_line` is on.
+pub const CURRENT_LINE_BACKGROUND: Color = Color::AnsiValue(236);
+
+/// Gives `line` the background `color` across `width` columns. The background is set
+/// again after each reset in the line, so that the styles in it, e.g. of the search
+/// results, are kept.
+pub fn set_line_background(line: &str, width: usize, color: Color) -> String {
+    let background = SetBackgroundColor(color).to_string();
+    let mut ret = background.clone();
+    let mut line = line.to_owned();
+    for reset in ["\x1b[0m", "\x1b[m", "\x1b[49m"] {
+        line = line.replace(reset, &format!("{}{}", reset, background));
+    }
+    ret.push_str(&line);
+    let padding = width.saturating_sub(textwrap::core::display_width(&line));
+    ret.push_str(&" ".repeat(padding));
+    ret.push_str(&ResetColor.to_string());
+    ret
+}
+
+/// Waits for the next terminal event. If `clock` is on, returns `None` when there is
+/// no event within `CLOCK_REFRESH_INTERVAL`, so that the clock can be redrawn.
+/// Otherwise it blocks until an event comes, keeping the CPU idle.
+pub fn read_event(clock: bool) -> std::io::Result<Option<Event>> {
+    if clock && !crossterm::event::poll(CLOCK_REFRESH_INTERVAL)? {
+        return Ok(None);
+    }
+    crossterm::event::read().map(Some)
+}
+
+/// Queues the current time at the right of the bottom row of the terminal.
+pub fn queue_clock(
+    output: &mut impl Write,
+    terminal_total_cols: u16,
+    terminal_total_rows: u16,
+) -> std::io::Result<()> {
+    let clock = Local::now().format("%H:%M").to_string();
+    crossterm::queue!(
+        output,
+        MoveTo(
+            terminal_total_cols.saturating_sub(CLOCK_WIDTH),
+            terminal_total_rows.saturating_sub(1)
+        ),
+        Print(clock.dark_grey())
+    )
+}
+
+/// Queues the prompt of the goto-date mode with `input` on the last row,
+/// with a hint of the accepted dates while `input` is empty.
+pub fn queue_goto_date_input(
+    output: &mut impl Write,
+    terminal_total_rows: u16,
+    input: &str,
+) -> std::io::Result<()> {
+    crossterm::queue!(
+        output,
+        MoveTo(0, terminal_total_rows.saturating_sub(1)),
+        Print("Go to date: "),
+        Print(input)
+    )?;
+    if input.is_empty() {
+        crossterm::queue!(
+            output,
+            Print("(e.g. 2024-05-01, 05-01 or yesterday)".dark_grey())
+        )?;
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use std::sync::LazyLock;
 
-    use crate::log_pager::utils::{get_char_index_by_line_index, get_line_index_by_char_index};
+    use crossterm::style::{Color, Stylize};
+
+    use crate::log_pager::utils::{
+        get_added_line_flags, get_char_index_by_line_index, get_line_index_by_char_index,
+        queue_clock, set_line_background, slice_colored_line, wrap_line,
+    };
 
     static TEST_LINES: LazyLock<Vec<String>> = LazyLock::new(|| {
         [
@@ -75,4 +226,66 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn test_get_added_line_flags() {
+        let old = "[2025-02-21 20:20] a\nb\nb";
+        let new = "[2025-02-21 20:20] a\nb\nc\nb\nb\n[2025-02-21 21:00] d";
+        assert_eq!(
+            get_added_line_flags(old, new),
+            [false, false, true, false, true, true]
+        );
+        assert_eq!(get_added_line_flags(new, ""), Vec::<bool>::new());
+    }
+
+    #[test]
+    fn test_slice_colored_line() {
+        assert_eq!(slice_colored_line("abcdef", 2, 3), "cde");
+        assert_eq!(slice_colored_line("abc", 5, 3), "");
+        // A wide character isn't split
+        assert_eq!(slice_colored_line("a日本", 2, 4), "本");
+        let line = format!("{}{}", "abc".green(), "def");
+        assert_eq!(
+            slice_colored_line(&line, 2, 2),
+            format!("{}{}", "c".green(), "d")
+        );
+    }
+
+    #[test]
+    fn test_wrap_line_keeps_indentation() {
+        assert_eq!(wrap_line("    return 1;", 80, true), ["    return 1;"]);
+        assert_eq!(wrap_line("    return 1;", 10, true), ["    return", "1;"]);
+        assert_eq!(wrap_line("    return 1;", 10, false), ["    return 1;"]);
+    }
+
+    #[test]
+    fn test_set_line_background() {
+        let background = "\x1b[48;5;236m";
+        assert_eq!(
+            set_line_background("ab", 4, Color::AnsiValue(236)),
+            format!("{}ab  \x1b[0m", background)
+        );
+
+        // The styles in the line are kept, and a reset doesn't remove the background
+        let line = format!("a{}b", "x".red());
+        assert_eq!(
+            set_line_background(&line, 3, Color::AnsiValue(236)),
+            format!("{}{}\x1b[0m", background, line)
+        );
+        assert_eq!(
+            set_line_background("a\x1b[0mb", 2, Color::AnsiValue(236)),
+            format!("{0}a\x1b[0m{0}b\x1b[0m", background)
+        );
+    }
+
+    #[test]
+    fn test_queue_clock() {
+        let mut output = Vec::new();
+        queue_clock(&mut output, 80, 24).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        // Moves to the 76th column of the 24th row, then prints the dark grey `%H:%M`
+        let clock = output.strip_prefix("\x1b[24;76H\x1b[38;5;8m").unwrap();
+        let clock = clock.strip_suffix("\x1b[39m").unwrap();
+        assert!(chrono::NaiveTime::parse_from_str(clock, "%H:%M").is_ok());
+    }
 }
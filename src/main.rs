@@ -1,117 +1,397 @@
+use std::io::{IsTerminal, Read};
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::ExitCode;
-use std::str::FromStr;
 
 use crate::error::Error;
-use chrono::NaiveDate;
+use chrono::{NaiveDate, NaiveDateTime};
 use clap::Parser;
 use config::config_file_path;
-use config::{construct_log_file_path, Config};
-use log_item::LogItem;
+use config::{construct_log_file_path, get_all_log_dates, get_log_dates, Config, LogConfig};
+use constants::CONFIG_PATH_KEY;
+use log_item::{LogItem, LogItemList, WordCountRange};
+use log_pager::date_picker::DatePicker;
 use log_pager::paging_all_pager::PagingAllPager;
 use log_pager::single_date_pager::SingleDatePager;
-use utils::fs::append_str_to_file;
-use utils::time::{date_time_now, get_today_date};
+use theme::Theme;
+use tidy::parse_log_content;
+use utils::editor::{edit_file, find_editor};
+use utils::file_manager::open_in_file_manager;
+use utils::fs::{
+    append_str_to_file, open_locked, overwrite_file, read_to_string_lossy, remove_file_if_blank,
+};
+use utils::output::{log_items_summary, print_or_page, write_log_items_with_template, Output};
+use utils::time::{date_time_now, format_duration, get_today_date, parse_date_from_str};
 
+pub mod archive;
+pub mod calendar;
 pub mod cli;
 pub mod config;
 pub mod constants;
+pub mod diff;
 pub mod error;
+pub mod export;
+pub mod grep;
+pub mod import;
 pub mod log_item;
 pub mod log_pager;
+pub mod on_this_day;
+pub mod recent;
+pub mod show;
+pub mod stats;
+pub mod streak;
+pub mod theme;
+pub mod tidy;
 pub mod utils;
+pub mod words;
 
-fn paging_log_file_by_date(log_dir_path: &PathBuf, date: NaiveDate, verbose: bool) {
+fn paging_log_file_by_date(
+    log_dir_path: &PathBuf,
+    date: NaiveDate,
+    verbose: bool,
+    bottom: bool,
+    config: &Config,
+) {
     let mut log_pager = SingleDatePager::new(date, log_dir_path.to_owned());
     log_pager.set_verbose(verbose);
+    log_pager.set_display_config(config.display.clone());
+    log_pager.set_theme(Theme::from_config(&config.theme));
+    log_pager.set_prompt_format(config.log.prompt_format.clone());
+    log_pager.set_date_input_formats(config.log.date_input_formats.clone());
+    log_pager.set_yearless_dates_in_past(config.log.yearless_dates_in_past);
+    log_pager.set_editor(config.log.editor.clone());
+    if bottom {
+        log_pager.start_at_end();
+    }
     log_pager.run();
 }
 
-fn parse_date_from_str(date_str: &str) -> Result<NaiveDate, chrono::ParseError> {
-    let today = get_today_date();
-    let date_fmt = "%Y-%m-%d";
-    NaiveDate::parse_from_str(date_str, date_fmt).or(NaiveDate::parse_from_str(
-        &format!("{}-{}", today.format("%Y"), date_str),
-        date_fmt,
-    ))
+/// Lets the user pick one of the dates with log files, and views its logs.
+fn pick_and_view_logs(
+    verbose: bool,
+    bottom: bool,
+    log_dir_path: &PathBuf,
+    config: &Config,
+) -> Result<(), Error> {
+    if !log_dir_path.exists() {
+        return Err(Error::LogDirNotFound(log_dir_path.to_owned()));
+    }
+    let dates = get_all_log_dates(log_dir_path).map_err(Error::Io)?;
+    if dates.is_empty() {
+        eprintln!("There are no logs to pick from");
+        return Ok(());
+    }
+    let mut date_picker = DatePicker::new(dates);
+    date_picker.set_display_config(config.display.clone());
+    if let Some(date) = date_picker.run() {
+        paging_log_file_by_date(log_dir_path, date, verbose, bottom, config);
+    }
+    Ok(())
+}
+
+fn parse_date_arg(date_str: String, log_config: &LogConfig) -> Result<NaiveDate, Error> {
+    parse_date_from_str(
+        &date_str,
+        &log_config.date_input_formats,
+        log_config.yearless_dates_in_past,
+    )
+    .map_err(|error| Error::DateParse {
+        input: date_str,
+        source: error,
+        formats: log_config.date_input_formats.clone(),
+    })
+}
+
+/// Returns the contents of the log files of `dates`, one after another, to be printed
+/// instead of paged. The missing log files are skipped.
+fn read_log_files(log_dir_path: &Path, dates: &[NaiveDate]) -> Result<String, Error> {
+    let mut content = String::new();
+    for date in dates {
+        let file_path = construct_log_file_path(log_dir_path, date);
+        let file_content = match read_to_string_lossy(&file_path) {
+            Ok((file_content, _)) => file_content,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(error) => return Err(Error::from_io(error, &file_path)),
+        };
+        content.push_str(&file_content);
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+    }
+    Ok(content)
+}
+
+fn view_all_logs<P: AsRef<Path>>(
+    search: Option<String>,
+    limit: Option<usize>,
+    exclude_today: bool,
+    log_dir_path: P,
+    config: &Config,
+) -> Result<(), Error> {
+    if !log_dir_path.as_ref().exists() {
+        return Err(Error::LogDirNotFound(log_dir_path.as_ref().into()));
+    }
+
+    // The pager needs a terminal, so the logs are printed or given to `$PAGER` instead
+    if !std::io::stdout().is_terminal() {
+        let mut dates = get_log_dates(log_dir_path.as_ref(), exclude_today).map_err(Error::Io)?;
+        if let Some(limit) = limit {
+            dates.drain(..dates.len().saturating_sub(limit));
+        }
+        let content = read_log_files(log_dir_path.as_ref(), &dates)?;
+        return print_or_page(&content).map_err(Error::Io);
+    }
+
+    let mut log_pager = PagingAllPager::new(log_dir_path.as_ref().to_path_buf());
+    log_pager.set_display_config(config.display.clone());
+    log_pager.set_theme(Theme::from_config(&config.theme));
+    log_pager.set_prompt_format(config.log.prompt_format.clone());
+    log_pager.set_date_input_formats(config.log.date_input_formats.clone());
+    log_pager.set_yearless_dates_in_past(config.log.yearless_dates_in_past);
+    log_pager.set_editor(config.log.editor.clone());
+    if limit.is_some() {
+        log_pager.set_day_limit(limit);
+    }
+    if exclude_today {
+        log_pager.set_exclude_today(true);
+    }
+    if let Some(search) = search {
+        log_pager.set_search(&search);
+    }
+    log_pager.run();
+    Ok(())
 }
 
 fn view_logs<P: AsRef<Path>>(
     date_str: Option<String>,
-    all: bool,
     verbose: bool,
+    bottom: bool,
     log_dir_path: P,
+    config: &Config,
 ) -> Result<(), Error> {
     let today_date = get_today_date();
     if !log_dir_path.as_ref().exists() {
         return Err(Error::LogDirNotFound(log_dir_path.as_ref().into()));
     }
 
-    if all {
-        PagingAllPager::new(log_dir_path.as_ref().to_path_buf()).run();
-        return Ok(());
-    }
-
     let date = match date_str {
-        Some(date_str) => parse_date_from_str(&date_str).map_err(|error| Error::DateParse {
-            input: date_str,
-            source: error,
-        })?,
+        Some(date_str) => parse_date_arg(date_str, &config.log)?,
         // Default date is today
         None => today_date,
     };
 
-    paging_log_file_by_date(&log_dir_path.as_ref().to_path_buf(), date, verbose);
+    // The pager needs a terminal, so the logs are printed or given to `$PAGER` instead
+    if !std::io::stdout().is_terminal() {
+        let content = read_log_files(log_dir_path.as_ref(), &[date])?;
+        return print_or_page(&content).map_err(Error::Io);
+    }
+
+    paging_log_file_by_date(
+        &log_dir_path.as_ref().to_path_buf(),
+        date,
+        verbose,
+        bottom,
+        config,
+    );
     Ok(())
 }
 
-fn write_log(log_content: &str, verbose: bool, log_dir_path: &Path) -> Result<(), Error> {
+/// The result of writing a log message.
+#[derive(Debug)]
+pub struct WrittenLog {
+    /// The log file that the log message was appended to
+    pub path: PathBuf,
+    /// The number of bytes appended to the log file
+    pub bytes: usize,
+    /// The date and time of the written log messages
+    pub date_time: NaiveDateTime,
+    /// The date and time of the log item before the written ones in the log file,
+    /// or None if they are the first of the day
+    pub previous_date_time: Option<NaiveDateTime>,
+}
+
+/// Checks the length of the log message against `max_entry_chars`. A longer message
+/// is an error if `strict` is true, otherwise only a warning is printed.
+fn check_log_message_length(
+    log_item: &LogItem,
+    max_entry_chars: Option<usize>,
+    strict: bool,
+) -> Result<(), Error> {
+    let Some(max_entry_chars) = max_entry_chars else {
+        return Ok(());
+    };
+    let chars = log_item.content().chars().count();
+    if chars <= max_entry_chars {
+        return Ok(());
+    }
+    if strict {
+        return Err(Error::LogMessageTooLong {
+            chars,
+            max_entry_chars,
+        });
+    }
+    eprintln!(
+        "warning: the log message has {} characters, more than `log.max_entry_chars` ({})",
+        chars, max_entry_chars
+    );
+    Ok(())
+}
+
+/// Writes the log messages to today's log file, in order. They're always stamped with
+/// the current time, so that no written log item is later than `date_time_now()`.
+///
+/// All messages share the same time stamp, which has a precision of minutes anyway.
+/// Their order is kept by the order in the file, which sorting by time doesn't change.
+///
+/// If `day` is an earlier date, the messages are stamped with the current time of that
+/// day instead, and inserted into its log file in chronological order.
+///
+/// The length of each message is checked before `log.entry_prefix` and `log.entry_suffix` are added.
+fn write_logs(
+    log_contents: &[&str],
+    verbose: bool,
+    log_dir_path: &Path,
+    log_config: &LogConfig,
+    strict: bool,
+    day: Option<NaiveDate>,
+) -> Result<WrittenLog, Error> {
     let date_time_now = date_time_now();
     let today_date = date_time_now.date();
+    let date = day.unwrap_or(today_date);
+    if date > today_date {
+        return Err(Error::FutureDate(date));
+    }
+    let date_time = date.and_time(date_time_now.time());
 
     if !log_dir_path.exists() {
         return Err(Error::LogDirNotFound(log_dir_path.to_path_buf()));
     }
 
-    let log_file_path = construct_log_file_path(log_dir_path, &today_date);
-
-    // If the log file does not exist, create it
-    let _ = std::fs::File::create_new(&log_file_path);
+    let log_file_path = construct_log_file_path(log_dir_path, &date);
+    // The log file is read only to find the previous log item, so an unreadable one is skipped
+    let previous_date_time =
+        LogItemList::from_log_file(&log_file_path)
+            .ok()
+            .and_then(|log_item_list| {
+                log_item_list
+                    .iter()
+                    .map(|log_item| *log_item.date_time())
+                    .filter(|x| *x <= date_time)
+                    .max()
+            });
 
-    let log_item = LogItem::new(date_time_now, log_content);
+    let mut log_items: Vec<LogItem> = Vec::new();
+    let mut content = String::new();
+    for log_content in log_contents {
+        let mut log_item = LogItem::new(date_time, log_content);
+        check_log_message_length(&log_item, log_config.max_entry_chars, strict)?;
+        log_item.add_affixes(&log_config.entry_prefix, &log_config.entry_suffix);
+        if verbose {
+            println!("Log info: {:#?}", log_item);
+        }
+        content.push_str(&log_item.to_string());
+        log_items.push(log_item);
+    }
     if verbose {
-        println!("Log info: {:#?}\nWriting the log message...", log_item);
+        println!("Writing the log message...");
     }
 
-    append_str_to_file(&log_file_path, &log_item.to_string()).map_err(Error::Io)?;
+    if date == today_date {
+        let bytes = append_str_to_file(&log_file_path, &content)
+            .map_err(|error| Error::from_io(error, &log_file_path))?;
+        return Ok(WrittenLog {
+            path: log_file_path,
+            bytes,
+            date_time,
+            previous_date_time,
+        });
+    }
 
-    if verbose {
-        println!(
-            r#"Written the log message to "{}""#,
-            log_file_path.display()
-        );
-    } else {
-        println!(
-            r#"Written the log message to "{}""#,
-            log_file_path
-                .file_name()
-                .expect("Isn't a filename")
-                .to_str()
-                .expect("Invalid Unicode")
+    // The log file stays locked while it's rewritten, so that no concurrent write is lost
+    let mut file =
+        open_locked(&log_file_path).map_err(|error| Error::from_io(error, &log_file_path))?;
+    let mut file_content = String::new();
+    file.read_to_string(&mut file_content)
+        .map_err(|error| Error::from_io(error, &log_file_path))?;
+    let mut log_item_list = parse_log_content(&file_content)
+        .ok_or_else(|| Error::LeadingText(log_file_path.clone()))?;
+    let mut bytes = 0;
+    let mut skipped = 0;
+    for log_item in log_items {
+        let len = log_item.to_string().len();
+        if log_item_list.insert_sorted(log_item) {
+            bytes += len;
+        } else {
+            skipped += 1;
+        }
+    }
+    if skipped > 0 {
+        eprintln!(
+            "warning: skipped {} log message(s) with the same time and content as an existing one",
+            skipped
         );
     }
+    overwrite_file(&mut file, &log_item_list.to_string())
+        .map_err(|error| Error::from_io(error, &log_file_path))?;
+
+    Ok(WrittenLog {
+        path: log_file_path,
+        bytes,
+        date_time,
+        previous_date_time,
+    })
+}
+
+/// Removes the last log message written today. Nothing is printed on success if `quiet` is true.
+fn undo_log(log_dir_path: &Path, quiet: bool) -> Result<(), Error> {
+    let today_date = get_today_date();
+
+    if !log_dir_path.exists() {
+        return Err(Error::LogDirNotFound(log_dir_path.to_path_buf()));
+    }
+
+    let log_file_path = construct_log_file_path(log_dir_path, &today_date);
+    // The log file stays locked while it's rewritten, so that no concurrent write is lost
+    let mut file =
+        open_locked(&log_file_path).map_err(|error| Error::from_io(error, &log_file_path))?;
+    let mut file_content = String::new();
+    file.read_to_string(&mut file_content)
+        .map_err(|error| Error::from_io(error, &log_file_path))?;
+
+    let mut log_item_list = parse_log_content(&file_content)
+        .ok_or_else(|| Error::LeadingText(log_file_path.clone()))?;
+    let log_item = log_item_list.pop();
+    if log_item.is_some() {
+        overwrite_file(&mut file, &log_item_list.to_string())
+            .map_err(|error| Error::from_io(error, &log_file_path))?;
+    }
+    drop(file);
+    // The log file is also created by `open_locked` if it doesn't exist
+    remove_file_if_blank(&log_file_path).map_err(|error| Error::from_io(error, &log_file_path))?;
+
+    let Some(log_item) = log_item else {
+        if !quiet {
+            println!("There is no log message written today.");
+        }
+        return Ok(());
+    };
+
+    if !quiet {
+        print!("Removed the log message:\n{}", log_item);
+    }
     Ok(())
 }
 
-fn edit_logs(date_str: Option<String>, verbose: bool, log_dir_path: &Path) -> Result<(), Error> {
+fn edit_logs(
+    date_str: Option<String>,
+    verbose: bool,
+    log_dir_path: &Path,
+    log_config: &LogConfig,
+) -> Result<(), Error> {
     let today_date = get_today_date();
 
     let date = match date_str {
-        Some(date_str) => parse_date_from_str(&date_str).map_err(|error| Error::DateParse {
-            input: date_str,
-            source: error,
-        })?,
+        Some(date_str) => parse_date_arg(date_str, log_config)?,
         // Default date is today
         None => today_date,
     };
@@ -122,56 +402,230 @@ fn edit_logs(date_str: Option<String>, verbose: bool, log_dir_path: &Path) -> Re
 
     let log_file_path = construct_log_file_path(log_dir_path, &date);
 
-    // If the log file does not exist, create it
-    let _ = std::fs::File::create_new(&log_file_path);
-
+    let editor = find_editor(&log_config.editor)?;
     if verbose {
-        let editor_path_res = edit::get_editor();
-        match editor_path_res {
-            Ok(editor_path) => {
-                println!("Opening editor: {}", editor_path.display());
-            }
-            Err(_) => {
-                println!("Can't find the editor");
-            }
-        }
+        println!("Opening editor: {}", editor);
     }
 
-    edit::edit_file(log_file_path).map_err(Error::Io)
+    edit_file(&log_file_path, &log_config.editor).map_err(Error::Io)?;
+
+    // Don't leave an empty file behind if nothing was written
+    remove_file_if_blank(&log_file_path).map_err(|error| Error::from_io(error, &log_file_path))?;
+    Ok(())
 }
 
-fn run() -> Result<(), Error> {
+/// Runs the command and returns the exit code for the successful cases.
+fn run() -> Result<ExitCode, Error> {
     // Command line parameters
     let cli = cli::Cli::parse();
 
-    Config::create_config_file_if_not_exists()?;
-    let config_dir_path = match config::config_dir_path() {
-        Some(result) => result,
+    let config_file_path = match cli.config {
+        // A config file given explicitly is never created, to catch typos in its path
+        Some(config_file_path) => {
+            if !config_file_path.exists() {
+                return Err(Error::ConfigFileNotFound(config_file_path));
+            }
+            config_file_path
+        }
         None => {
-            return Err(Error::DetermineConfigDir);
+            Config::create_config_file_if_not_exists(cli.quiet)?;
+            let config_dir_path = match config::config_dir_path() {
+                Some(result) => result,
+                None => {
+                    return Err(Error::DetermineConfigDir);
+                }
+            };
+            config_file_path(&config_dir_path)
         }
     };
-    let config_file_path = config_file_path(&config_dir_path);
+    // A broken config file can't be loaded, so it's repaired before that
+    if let cli::Commands::Config { repair: true, .. } = cli.command {
+        match config::repair_config_file(&config_file_path)? {
+            Some(backup_file_path) if !cli.quiet => println!(
+                "Backed up the config file to `{}` and regenerated `{}`",
+                backup_file_path.display(),
+                config_file_path.display()
+            ),
+            None if !cli.quiet => println!("The config file is fine. Nothing to repair."),
+            _ => {}
+        }
+        return Ok(ExitCode::SUCCESS);
+    }
     let config = config::Config::from_config_file(config_file_path.as_path())?;
-    let log_dir_path = PathBuf::from_str(&config.log.dir).expect("Incorrect path");
+    let log_dir_path = config::log_dir_path(&config.log);
 
+    let mut exit_code = ExitCode::SUCCESS;
     match cli.command {
-        cli::Commands::View { date, verbose, all } => {
-            view_logs(date, all, verbose, &log_dir_path)?;
+        cli::Commands::View {
+            date,
+            verbose,
+            all,
+            no_altscreen,
+            no_wrap,
+            sorted,
+            search,
+            limit,
+            exclude_today,
+            bottom,
+            stdin,
+            pick,
+        } => {
+            let mut config = config;
+            if no_altscreen {
+                config.display.alternate_screen = false;
+            }
+            if no_wrap {
+                config.display.wrap = false;
+            }
+            if sorted {
+                config.display.sort_entries = true;
+            }
+            if stdin {
+                let mut content = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut content)
+                    .map_err(Error::Io)?;
+                if std::io::stdout().is_terminal() {
+                    let mut log_pager = SingleDatePager::new(get_today_date(), log_dir_path);
+                    log_pager.set_display_config(config.display.clone());
+                    log_pager.set_theme(Theme::from_config(&config.theme));
+                    log_pager.set_prompt_format(config.log.prompt_format.clone());
+                    log_pager.set_piped_content(content);
+                    if bottom {
+                        log_pager.start_at_end();
+                    }
+                    log_pager.run();
+                } else {
+                    print_or_page(&content).map_err(Error::Io)?;
+                }
+            } else if pick {
+                pick_and_view_logs(verbose, bottom, &log_dir_path, &config)?;
+            } else if all {
+                view_all_logs(search, limit, exclude_today, &log_dir_path, &config)?;
+            } else {
+                view_logs(date, verbose, bottom, &log_dir_path, &config)?;
+            }
         }
-        cli::Commands::Write { message, verbose } => {
-            let message_string = if let Some(message_string) = message {
-                message_string
+        cli::Commands::Write {
+            message,
+            lines,
+            strict,
+            day,
+            template_file,
+            since_last,
+            verbose,
+        } => {
+            let mut config = config;
+            if let Some(template_file) = template_file {
+                config.log.template = std::fs::read_to_string(&template_file).map_err(|error| {
+                    Error::Io(std::io::Error::new(
+                        error.kind(),
+                        format!(
+                            "Unable to read the template file '{}': {}",
+                            template_file.display(),
+                            error
+                        ),
+                    ))
+                })?;
+            }
+            let day = day
+                .map(|day| parse_date_arg(day, &config.log))
+                .transpose()?;
+            let message_strings: Vec<String> = if lines {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(Error::Io)?;
+                input
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| line.to_owned())
+                    .collect()
+            } else if message.is_empty() {
+                vec![input_log_message(&config.log)?]
             } else {
-                input_log_message()
+                // Like `git commit -m`, each message is a paragraph
+                vec![message.join("\n\n")]
             };
 
-            if message_string.trim().is_empty() {
+            if message_strings.iter().all(|x| x.trim().is_empty()) {
                 return Err(Error::EmptyLogMessage);
             }
-            write_log(&message_string, verbose, &log_dir_path)?;
+            let log_contents: Vec<&str> = message_strings.iter().map(|x| x.as_str()).collect();
+            let written_log = write_logs(
+                &log_contents,
+                verbose,
+                &log_dir_path,
+                &config.log,
+                strict,
+                day,
+            )?;
+            let messages = if log_contents.len() == 1 {
+                "the log message".to_owned()
+            } else {
+                format!("{} log messages", log_contents.len())
+            };
+            if cli.quiet {
+                return Ok(exit_code);
+            }
+            if verbose {
+                println!(
+                    r#"Written {} bytes of {} to "{}""#,
+                    written_log.bytes,
+                    messages,
+                    written_log.path.display()
+                );
+            } else {
+                println!(
+                    r#"Written {} to "{}""#,
+                    messages,
+                    written_log
+                        .path
+                        .file_name()
+                        .expect("Isn't a filename")
+                        .to_str()
+                        .expect("Invalid Unicode")
+                );
+            }
+            if since_last || verbose {
+                match written_log.previous_date_time {
+                    Some(previous_date_time) => println!(
+                        "Time since last entry: {}",
+                        format_duration(written_log.date_time - previous_date_time)
+                    ),
+                    None => println!("This is the first entry of the day."),
+                }
+            }
+        }
+        cli::Commands::Config { dump: true, .. } => {
+            // `log.dir` is shown as resolved, e.g. overridden by the environment
+            let mut config = config;
+            config.log.dir = log_dir_path.display().to_string();
+            print!("{}", config.to_toml_string()?);
+        }
+        cli::Commands::Config {
+            key: Some(key),
+            value,
+            unset,
+            ..
+        } if key == CONFIG_PATH_KEY => {
+            if value.is_some() || unset {
+                return Err(Error::ReadOnlyKey(key));
+            }
+            println!("{}", config_file_path.display());
         }
-        cli::Commands::Config { key, value } => match value {
+        cli::Commands::Config {
+            key: Some(key),
+            unset: true,
+            ..
+        } => {
+            config::unset_by_key(&config_file_path, &key)?;
+        }
+        cli::Commands::Config {
+            key: Some(key),
+            value,
+            ..
+        } => match value {
             Some(value) => {
                 config::set_by_key(&config_file_path, &key, value)?;
             }
@@ -183,86 +637,645 @@ fn run() -> Result<(), Error> {
                 }
             }
         },
+        cli::Commands::Config { key: None, .. } => {
+            unreachable!("`config` without a key is `config --repair` or `config --dump`, which are handled above")
+        }
+        cli::Commands::Dir { open } => {
+            if !log_dir_path.exists() {
+                return Err(Error::LogDirNotFound(log_dir_path));
+            }
+            if open {
+                open_in_file_manager(&log_dir_path).map_err(Error::Io)?;
+            } else {
+                println!("{}", log_dir_path.display());
+            }
+        }
+        cli::Commands::Path {
+            date,
+            all,
+            exists_only,
+        } => {
+            if all {
+                if !log_dir_path.exists() {
+                    return Err(Error::LogDirNotFound(log_dir_path));
+                }
+                for date in get_all_log_dates(&log_dir_path).map_err(Error::Io)? {
+                    let file_path = construct_log_file_path(&log_dir_path, &date);
+                    println!(
+                        "{}",
+                        std::path::absolute(file_path).map_err(Error::Io)?.display()
+                    );
+                }
+            } else {
+                let date = match date {
+                    Some(date_str) => parse_date_arg(date_str, &config.log)?,
+                    None => get_today_date(),
+                };
+                let file_path = construct_log_file_path(&log_dir_path, &date);
+                let file_path = std::path::absolute(file_path).map_err(Error::Io)?;
+                if exists_only && !file_path.exists() {
+                    exit_code = ExitCode::from(1);
+                } else {
+                    println!("{}", file_path.display());
+                }
+            }
+        }
+        cli::Commands::Undo => {
+            undo_log(&log_dir_path, cli.quiet)?;
+        }
+        cli::Commands::Stats {
+            by,
+            json,
+            exclude_today,
+        } => {
+            stats::print_stats(
+                &log_dir_path,
+                by,
+                json,
+                config.display.hide_empty_entries,
+                exclude_today,
+            )?;
+        }
+        cli::Commands::Streak {
+            json,
+            exclude_today,
+        } => {
+            streak::print_streaks(
+                &log_dir_path,
+                json,
+                config.display.hide_empty_entries,
+                exclude_today,
+            )?;
+        }
+        cli::Commands::Words { top, no_stopwords } => {
+            words::print_word_frequencies(&log_dir_path, top, !no_stopwords)?;
+        }
+        cli::Commands::Calendar { month } => {
+            let month = match month {
+                Some(month) => calendar::parse_month(&month)?,
+                None => get_today_date(),
+            };
+            calendar::print_calendar(
+                &log_dir_path,
+                month,
+                config.display.first_weekday,
+                config.display.locale,
+            )?;
+        }
+        cli::Commands::OnThisDay { date } => {
+            let date = match date {
+                Some(date) => parse_date_arg(date, &config.log)?,
+                None => get_today_date(),
+            };
+            on_this_day::print_on_this_day(
+                &log_dir_path,
+                date,
+                &Theme::from_config(&config.theme),
+            )?;
+        }
+        cli::Commands::Diff { old_date, new_date } => {
+            let old_date = parse_date_arg(old_date, &config.log)?;
+            let new_date = parse_date_arg(new_date, &config.log)?;
+            diff::print_diff(&log_dir_path, old_date, new_date)?;
+        }
+        cli::Commands::Tidy { date, all, dry_run } => {
+            if !log_dir_path.exists() {
+                return Err(Error::LogDirNotFound(log_dir_path));
+            }
+            let date = match date {
+                Some(date) => parse_date_arg(date, &config.log)?,
+                None => get_today_date(),
+            };
+            let dates = tidy::selected_log_dates(&log_dir_path, date, all)?;
+            tidy::tidy_log_files(&log_dir_path, &dates, dry_run, cli.quiet)?;
+        }
+        cli::Commands::Archive {
+            before,
+            to,
+            dry_run,
+        } => {
+            if !log_dir_path.exists() {
+                return Err(Error::LogDirNotFound(log_dir_path));
+            }
+            let before = parse_date_arg(before, &config.log)?;
+            archive::archive_log_files(&log_dir_path, before, &to, dry_run, cli.quiet)?;
+        }
+        cli::Commands::Backup { path } => {
+            archive::backup_log_files(&log_dir_path, &path, cli.quiet)?;
+        }
+        cli::Commands::Dedupe { date, all, dry_run } => {
+            if !log_dir_path.exists() {
+                return Err(Error::LogDirNotFound(log_dir_path));
+            }
+            let date = match date {
+                Some(date) => parse_date_arg(date, &config.log)?,
+                None => get_today_date(),
+            };
+            let dates = tidy::selected_log_dates(&log_dir_path, date, all)?;
+            tidy::dedupe_log_files(&log_dir_path, &dates, dry_run, cli.quiet)?;
+        }
+        cli::Commands::Grep {
+            pattern,
+            ignore_case,
+            date,
+            min_words,
+            max_words,
+            count_only,
+            count_by_day,
+            template,
+            summary,
+            output,
+        } => {
+            if !log_dir_path.exists() {
+                return Err(Error::LogDirNotFound(log_dir_path));
+            }
+            let date = date
+                .map(|date_str| parse_date_arg(date_str, &config.log))
+                .transpose()?;
+            let regex = grep::build_search_regex(&pattern, ignore_case)?;
+            let word_count = WordCountRange {
+                min: min_words,
+                max: max_words,
+            };
+            let log_items =
+                grep::grep_log_items(&log_dir_path, &regex, date, word_count).map_err(Error::Io)?;
+            let mut output = Output::new(output.as_deref()).map_err(Error::Io)?;
+            if count_only || count_by_day {
+                grep::write_grep_count(&mut output, &log_items, count_by_day).map_err(Error::Io)?;
+            } else {
+                match template {
+                    Some(template) => {
+                        write_log_items_with_template(&mut output, &log_items, &template)
+                    }
+                    None => grep::write_grep_result(
+                        &mut output,
+                        &regex,
+                        &log_items,
+                        &Theme::from_config(&config.theme),
+                    ),
+                }
+                .map_err(Error::Io)?;
+            }
+            if summary && !cli.quiet {
+                eprintln!("{}", log_items_summary(&log_items));
+            }
+            // Like grep, exit with 1 if nothing matches.
+            if log_items.is_empty() {
+                exit_code = ExitCode::from(1);
+            }
+        }
+        cli::Commands::Recent {
+            count,
+            entries,
+            template,
+            summary,
+        } => {
+            if !log_dir_path.exists() {
+                return Err(Error::LogDirNotFound(log_dir_path));
+            }
+            let count = entries.unwrap_or(count);
+            let log_items = recent::recent_log_items(&log_dir_path, count).map_err(Error::Io)?;
+            let mut output = Output::new(None).map_err(Error::Io)?;
+            match template {
+                Some(template) => write_log_items_with_template(&mut output, &log_items, &template),
+                None => recent::write_recent_log_items(
+                    &mut output,
+                    &log_items,
+                    &Theme::from_config(&config.theme),
+                ),
+            }
+            .map_err(Error::Io)?;
+            if summary && !cli.quiet {
+                eprintln!("{}", log_items_summary(&log_items));
+            }
+        }
+        cli::Commands::Last { with_date } => {
+            if !log_dir_path.exists() {
+                return Err(Error::LogDirNotFound(log_dir_path));
+            }
+            match show::find_last_log_item(&log_dir_path).map_err(Error::Io)? {
+                Some(log_item) if with_date => print!("{}", log_item),
+                Some(log_item) => println!("{}", log_item.content()),
+                None => exit_code = ExitCode::from(1),
+            }
+        }
+        cli::Commands::Show { id } => {
+            if !log_dir_path.exists() {
+                return Err(Error::LogDirNotFound(log_dir_path));
+            }
+            let log_items = show::find_log_items_by_id(&log_dir_path, &id)?;
+            if log_items.is_empty() {
+                return Err(Error::LogItemNotFound(id));
+            }
+            for log_item in log_items {
+                print!("{}", log_item);
+            }
+        }
+        cli::Commands::Export {
+            format,
+            since,
+            until,
+            flat,
+            min_words,
+            max_words,
+            exclude_today,
+            output,
+        } => {
+            if !log_dir_path.exists() {
+                return Err(Error::LogDirNotFound(log_dir_path));
+            }
+            let options = export::ExportOptions {
+                format,
+                since: since
+                    .map(|since| parse_date_arg(since, &config.log))
+                    .transpose()?,
+                until: until
+                    .map(|until| parse_date_arg(until, &config.log))
+                    .transpose()?,
+                flat,
+                exclude_today,
+                word_count: WordCountRange {
+                    min: min_words,
+                    max: max_words,
+                },
+            };
+            let mut output = Output::new(output.as_deref()).map_err(Error::Io)?;
+            export::export_logs(&log_dir_path, options, &mut output).map_err(Error::Io)?;
+        }
+        cli::Commands::Import {
+            file,
+            format,
+            dry_run,
+        } => {
+            if !log_dir_path.exists() {
+                return Err(Error::LogDirNotFound(log_dir_path));
+            }
+            let log_items = import::read_import_file(&file, format)?;
+            let imported_days =
+                import::import_log_items(&log_dir_path, log_items, dry_run).map_err(Error::Io)?;
+            // The report of a dry run is its result, so it's printed even if quiet
+            if cli.quiet && !dry_run {
+                return Ok(exit_code);
+            }
+            for imported_day in imported_days {
+                println!(
+                    "{}: {} {}, {} already present",
+                    imported_day.date,
+                    imported_day.imported,
+                    if dry_run { "to import" } else { "imported" },
+                    imported_day.skipped
+                );
+            }
+        }
         cli::Commands::Edit { date, verbose } => {
-            edit_logs(date, verbose, &log_dir_path)?;
+            edit_logs(date, verbose, &log_dir_path, &config.log)?;
         }
     };
-    Ok(())
+    Ok(exit_code)
 }
 
 fn main() -> ExitCode {
-    if let Err(error) = run() {
-        eprintln!("{}", error);
-        ExitCode::FAILURE
-    } else {
-        ExitCode::SUCCESS
+    utils::terminal::install_panic_hook();
+    match run() {
+        Ok(exit_code) => exit_code,
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// The instructional lines inserted into the temporary file by `input_log_message`.
+const INPUT_TEMPLATE_LINES: [&str; 2] = [
+    "# Enter your log message here.",
+    "# Lines starting with '#' will be ignored.",
+];
+
+/// Returns the initial content of the temporary file: `log.template` followed by
+/// the instructional lines if `log.show_instructions` is enabled.
+fn initial_message_content(log_config: &LogConfig) -> String {
+    let mut ret = String::new();
+    let template = log_config.template.trim_end();
+    if !template.is_empty() {
+        ret.push_str(template);
+        ret.push('\n');
+    }
+    if log_config.show_instructions {
+        ret.push('\n');
+        ret.push_str(&INPUT_TEMPLATE_LINES.join("\n"));
+        ret.push('\n');
     }
+    ret
 }
 
-/// Opens a temporary file in the user's default editor, waits for editing to complete,
-/// reads the edited content, deletes the temporary file, and returns the content.
+/// Opens a temporary file pre-filled by `initial_message_content` in the user's default
+/// editor, waits for editing to complete, reads the edited content, deletes the temporary
+/// file, and returns the content.
+///
+/// If `log.strip_comments` is `false`, only the instructional lines are removed and
+/// the user's own lines starting with '#' are kept. An empty string is returned
+/// if the template is left unchanged.
 ///
 /// # Returns
 /// A `String` containing the content of the temporary file after the user finishes editing.
 ///
 /// # Errors
-/// Panics if there is an issue creating, reading, or editing the temporary file.
-fn input_log_message() -> String {
-    use std::fs::{self, File};
-    use std::io::{Read, Write};
-
-    use edit::edit_file;
+/// Returns an error if no editor is found, or if the temporary file can't be written,
+/// edited, read or deleted. The temporary file is deleted on the errors too.
+fn input_log_message(log_config: &LogConfig) -> Result<String, Error> {
+    find_editor(&log_config.editor)?;
 
     // Create a temporary file
-    let (mut temp_file, temp_file_path) = crate::utils::fs::create_unique_temp_file();
+    let (temp_file, temp_file_path) = crate::utils::fs::create_unique_temp_file();
+    let edited_content = edit_temp_file(temp_file, &temp_file_path, log_config);
 
-    // Optionally add an initial message
-    writeln!(
-        temp_file,
-        "\n# Enter your log message here.\n# Lines starting with '#' will be ignored."
-    )
-    .expect("Failed to write initial content to the temporary file");
-    drop(temp_file); // Close the file so it can be opened by the editor
+    // Delete the temporary file, whether the editing succeeded or not
+    let remove_result = std::fs::remove_file(&temp_file_path);
+    let edited_content = edited_content?;
+    remove_result.map_err(Error::Io)?;
 
-    // Open the file in the user's default editor
-    edit_file(&temp_file_path).expect("Failed to open the file in the editor");
+    let strip_comments = log_config.strip_comments;
+    let message = remove_comment_lines(&edited_content, strip_comments);
+    if message.trim() == remove_comment_lines(&log_config.template, strip_comments).trim() {
+        return Ok(String::new());
+    }
+    Ok(message)
+}
 
-    // Read the edited content
-    let mut edited_content = String::new();
-    let mut temp_file = File::open(&temp_file_path).expect("Failed to open the temporary file");
-    temp_file
-        .read_to_string(&mut edited_content)
-        .expect("Failed to read the content from the temporary file");
+/// Writes `initial_message_content` to the temporary file, opens it in the editor,
+/// and returns the edited content.
+fn edit_temp_file(
+    mut temp_file: std::fs::File,
+    temp_file_path: &Path,
+    log_config: &LogConfig,
+) -> Result<String, Error> {
+    use std::io::Write;
 
-    // Delete the temporary file
-    fs::remove_file(&temp_file_path).expect("Failed to delete the temporary file");
+    write!(temp_file, "{}", initial_message_content(log_config)).map_err(Error::Io)?;
+    drop(temp_file); // Close the file so it can be opened by the editor
+
+    edit_file(temp_file_path, &log_config.editor).map_err(Error::Io)?;
+    std::fs::read_to_string(temp_file_path).map_err(Error::Io)
+}
 
-    // Filter out comment lines
-    let cleaned_content: String = edited_content
+/// Removes the instructional lines from `content`, and every other line starting with '#'
+/// if `strip_comments` is `true`.
+fn remove_comment_lines(content: &str, strip_comments: bool) -> String {
+    content
         .lines()
-        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter(|line| {
+            if strip_comments {
+                !line.trim_start().starts_with('#')
+            } else {
+                !INPUT_TEMPLATE_LINES.contains(line)
+            }
+        })
         .collect::<Vec<_>>()
-        .join("\n");
-
-    cleaned_content
+        .join("\n")
 }
 
 #[cfg(test)]
 mod test {
-    use chrono::{Datelike, NaiveDate};
+    #[test]
+    fn test_remove_comment_lines() {
+        let content = "# TODO\nbuy milk\n# Enter your log message here.\n# Lines starting with '#' will be ignored.";
+        assert_eq!(super::remove_comment_lines(content, true), "buy milk");
+        assert_eq!(
+            super::remove_comment_lines(content, false),
+            "# TODO\nbuy milk"
+        );
+    }
+
+    #[test]
+    fn test_initial_message_content() {
+        let mut log_config = crate::LogConfig::default();
+        assert_eq!(
+            super::initial_message_content(&log_config),
+            "\n# Enter your log message here.\n# Lines starting with '#' will be ignored.\n"
+        );
 
-    use crate::utils::time::get_today_date;
+        log_config.template = "## Mood\n\n## Done today\n".to_owned();
+        assert_eq!(
+            super::initial_message_content(&log_config),
+            "## Mood\n\n## Done today\n\n# Enter your log message here.\n# Lines starting with '#' will be ignored.\n"
+        );
+
+        log_config.show_instructions = false;
+        assert_eq!(
+            super::initial_message_content(&log_config),
+            "## Mood\n\n## Done today\n"
+        );
+    }
+
+    #[test]
+    fn test_write_logs() {
+        use chrono::Timelike;
+
+        let log_dir = tempfile::tempdir().unwrap();
+        let mut log_config = crate::LogConfig::default();
+        let written_log = super::write_logs(
+            &["first", "second", "third"],
+            false,
+            log_dir.path(),
+            &log_config,
+            false,
+            None,
+        )
+        .unwrap();
+        let log_item_list = crate::LogItemList::from_log_file(&written_log.path).unwrap();
+        let contents: Vec<&str> = log_item_list.iter().map(|x| x.content()).collect();
+        assert_eq!(contents, ["first", "second", "third"]);
+        assert_eq!(written_log.previous_date_time, None);
+
+        log_config.entry_prefix = "- ".to_owned();
+        let first_date_time = written_log.date_time;
+        let written_log = super::write_logs(
+            &["fourth", "- fifth"],
+            false,
+            log_dir.path(),
+            &log_config,
+            false,
+            None,
+        )
+        .unwrap();
+        let log_item_list = crate::LogItemList::from_log_file(&written_log.path).unwrap();
+        let contents: Vec<&str> = log_item_list.iter().skip(3).map(|x| x.content()).collect();
+        assert_eq!(contents, ["- fourth", "- fifth"]);
+        // The timestamps have a precision of minutes
+        let first_date_time = first_date_time.with_second(0).unwrap().with_nanosecond(0);
+        assert_eq!(written_log.previous_date_time, first_date_time);
+
+        log_config.max_entry_chars = Some(1);
+        assert!(
+            super::write_logs(&["long"], false, log_dir.path(), &log_config, true, None).is_err()
+        );
+    }
+
+    #[test]
+    fn test_write_logs_to_earlier_day() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let log_config = crate::LogConfig::default();
+        let yesterday = crate::get_today_date().pred_opt().unwrap();
+        let log_file_path = crate::construct_log_file_path(log_dir.path(), &yesterday);
+        std::fs::write(
+            &log_file_path,
+            format!("[{} 00:00] first\n[{} 23:59] last\n", yesterday, yesterday),
+        )
+        .unwrap();
+
+        let written_log = super::write_logs(
+            &["forgotten"],
+            false,
+            log_dir.path(),
+            &log_config,
+            false,
+            Some(yesterday),
+        )
+        .unwrap();
+        assert_eq!(written_log.path, log_file_path);
+        let log_item_list = crate::LogItemList::from_log_file(&log_file_path).unwrap();
+        let contents: Vec<&str> = log_item_list.iter().map(|x| x.content()).collect();
+        assert_eq!(contents.len(), 3);
+        assert_eq!(contents[0], "first");
+        assert!(log_item_list
+            .iter()
+            .all(|log_item| log_item.date_time().date() == yesterday));
+
+        // Only the inserted messages are counted
+        let written_log = super::write_logs(
+            &["twice", "twice"],
+            false,
+            log_dir.path(),
+            &log_config,
+            false,
+            Some(yesterday),
+        )
+        .unwrap();
+        let log_item_list = crate::LogItemList::from_log_file(&log_file_path).unwrap();
+        assert_eq!(log_item_list.len(), 4);
+        let inserted = log_item_list
+            .iter()
+            .find(|log_item| log_item.content() == "twice")
+            .unwrap();
+        assert_eq!(written_log.bytes, inserted.to_string().len());
+
+        // The text before the first log item would be lost by rewriting the log file
+        let content = format!("notes\n[{} 00:00] first\n", yesterday);
+        std::fs::write(&log_file_path, &content).unwrap();
+        assert!(matches!(
+            super::write_logs(
+                &["forgotten"],
+                false,
+                log_dir.path(),
+                &log_config,
+                false,
+                Some(yesterday)
+            ),
+            Err(crate::Error::LeadingText(_))
+        ));
+        assert_eq!(std::fs::read_to_string(&log_file_path).unwrap(), content);
+
+        let tomorrow = crate::get_today_date().succ_opt().unwrap();
+        assert!(super::write_logs(
+            &["later"],
+            false,
+            log_dir.path(),
+            &log_config,
+            false,
+            Some(tomorrow)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_undo_log() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let today = crate::get_today_date();
+        let log_file_path = crate::construct_log_file_path(log_dir.path(), &today);
+        super::undo_log(log_dir.path(), true).unwrap();
+        assert!(!log_file_path.exists());
+
+        std::fs::write(
+            &log_file_path,
+            format!("[{} 00:00] first\n[{} 00:01] second\n", today, today),
+        )
+        .unwrap();
+        super::undo_log(log_dir.path(), true).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&log_file_path).unwrap(),
+            format!("[{} 00:00] first\n", today)
+        );
+        super::undo_log(log_dir.path(), true).unwrap();
+        assert!(!log_file_path.exists());
+
+        // The text before the first log item would be lost by rewriting the log file
+        let content = format!("notes\n[{} 00:00] first\n", today);
+        std::fs::write(&log_file_path, &content).unwrap();
+        assert!(matches!(
+            super::undo_log(log_dir.path(), true),
+            Err(crate::Error::LeadingText(_))
+        ));
+        assert_eq!(std::fs::read_to_string(&log_file_path).unwrap(), content);
+    }
+
+    #[test]
+    fn test_recent_entries() {
+        use clap::Parser;
+
+        let recent_count = |args: &[&str]| -> Result<usize, clap::Error> {
+            let cli = crate::cli::Cli::try_parse_from(args)?;
+            match cli.command {
+                crate::cli::Commands::Recent { count, entries, .. } => Ok(entries.unwrap_or(count)),
+                _ => unreachable!(),
+            }
+        };
+        assert_eq!(recent_count(&["mylog", "recent"]).unwrap(), 10);
+        assert_eq!(recent_count(&["mylog", "recent", "3"]).unwrap(), 3);
+        assert_eq!(recent_count(&["mylog", "recent", "-n", "4"]).unwrap(), 4);
+        assert_eq!(
+            recent_count(&["mylog", "recent", "--entries", "5"]).unwrap(),
+            5
+        );
+        assert!(recent_count(&["mylog", "recent", "3", "-n", "4"]).is_err());
+    }
+
+    #[test]
+    fn test_check_log_message_length() {
+        let log_item = crate::LogItem::new(chrono::NaiveDateTime::default(), "日记 abc");
+        assert!(super::check_log_message_length(&log_item, None, true).is_ok());
+        assert!(super::check_log_message_length(&log_item, Some(6), true).is_ok());
+        assert!(super::check_log_message_length(&log_item, Some(5), false).is_ok());
+        assert!(matches!(
+            super::check_log_message_length(&log_item, Some(5), true),
+            Err(crate::Error::LogMessageTooLong {
+                chars: 6,
+                max_entry_chars: 5
+            })
+        ));
+    }
 
     #[test]
-    fn test_parse_date_from_str() {
-        let today = get_today_date();
+    fn test_read_log_files() {
+        let log_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            log_dir.path().join("2024-05-01.log"),
+            "[2024-05-01 08:00] first",
+        )
+        .unwrap();
+        std::fs::write(
+            log_dir.path().join("2024-05-03.log"),
+            "[2024-05-03 09:00] second\n",
+        )
+        .unwrap();
+        let dates = ["2024-05-01", "2024-05-02", "2024-05-03"]
+            .map(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap());
         assert_eq!(
-            super::parse_date_from_str("2024-5-12"),
-            Ok(NaiveDate::from_ymd_opt(2024, 5, 12).unwrap())
+            super::read_log_files(log_dir.path(), &dates).unwrap(),
+            "[2024-05-01 08:00] first\n[2024-05-03 09:00] second\n"
         );
         assert_eq!(
-            super::parse_date_from_str("12-02"),
-            Ok(today.with_day(2).unwrap().with_month(12).unwrap())
+            super::read_log_files(log_dir.path(), &dates[1..2]).unwrap(),
+            ""
         );
     }
 }
@@ -0,0 +1,104 @@
+use std::{io::Write, path::Path};
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::{
+    config::{construct_log_file_path, get_all_log_dates},
+    error::Error,
+    log_item::{LogItem, LogItemList},
+    recent::write_recent_log_items,
+    theme::Theme,
+    utils::output::Output,
+};
+
+/// Returns the dates in `dates` with the same month and day as `date` in earlier years,
+/// newest first.
+pub fn same_day_in_earlier_years(dates: &[NaiveDate], date: NaiveDate) -> Vec<NaiveDate> {
+    dates
+        .iter()
+        .rev()
+        .filter(|x| x.month() == date.month() && x.day() == date.day() && x.year() < date.year())
+        .copied()
+        .collect()
+}
+
+/// Prints the log items written on the month and day of `date` in earlier years,
+/// grouped by year.
+pub fn print_on_this_day(log_dir_path: &Path, date: NaiveDate, theme: &Theme) -> Result<(), Error> {
+    if !log_dir_path.exists() {
+        return Err(Error::LogDirNotFound(log_dir_path.to_path_buf()));
+    }
+
+    let dates = get_all_log_dates(log_dir_path).map_err(Error::Io)?;
+    let mut output = Output::new(None).map_err(Error::Io)?;
+    let mut is_first_year = true;
+    for past_date in same_day_in_earlier_years(&dates, date) {
+        let file_path = construct_log_file_path(log_dir_path, &past_date);
+        let log_items: Vec<LogItem> = LogItemList::from_log_file(&file_path)
+            .map_err(Error::Io)?
+            .into_iter()
+            .collect();
+        if log_items.is_empty() {
+            continue;
+        }
+
+        if !is_first_year {
+            writeln!(output).map_err(Error::Io)?;
+        }
+        let years_ago = date.year() - past_date.year();
+        let plural = if years_ago == 1 { "" } else { "s" };
+        writeln!(
+            output,
+            "===== {} ({} year{} ago) =====",
+            past_date.year(),
+            years_ago,
+            plural
+        )
+        .map_err(Error::Io)?;
+        write_recent_log_items(&mut output, &log_items, theme).map_err(Error::Io)?;
+        is_first_year = false;
+    }
+
+    if is_first_year {
+        println!(
+            "There is no log message on {} in earlier years.",
+            date.format("%m-%d")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+
+    use super::same_day_in_earlier_years;
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_same_day_in_earlier_years() {
+        let dates = [
+            "2022-05-12",
+            "2023-05-11",
+            "2023-05-12",
+            "2024-02-29",
+            "2024-05-12",
+            "2025-05-12",
+        ]
+        .map(date);
+
+        assert_eq!(
+            same_day_in_earlier_years(&dates, date("2025-05-12")),
+            [date("2024-05-12"), date("2023-05-12"), date("2022-05-12")]
+        );
+        assert_eq!(
+            same_day_in_earlier_years(&dates, date("2024-05-12")),
+            [date("2023-05-12"), date("2022-05-12")]
+        );
+        assert!(same_day_in_earlier_years(&dates, date("2025-02-28")).is_empty());
+        assert!(same_day_in_earlier_years(&dates, date("2022-05-12")).is_empty());
+    }
+}
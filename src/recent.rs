@@ -0,0 +1,89 @@
+use std::{io::Write, path::Path};
+
+use crossterm::style::Stylize;
+
+use crate::{
+    config::{construct_log_file_path, get_all_log_dates},
+    log_item::{LogItem, LogItemList},
+    theme::Theme,
+    utils::output::Output,
+};
+
+/// Returns at most `count` log items in `log_dir_path`, newest first.
+/// Of the log items with the same date time, the one written later comes first.
+pub fn recent_log_items(log_dir_path: &Path, count: usize) -> std::io::Result<Vec<LogItem>> {
+    let mut ret: Vec<LogItem> = Vec::new();
+    for date in get_all_log_dates(log_dir_path)?.into_iter().rev() {
+        if ret.len() >= count {
+            break;
+        }
+        let file_path = construct_log_file_path(log_dir_path, &date);
+        // Reversed, so that the stable sort keeps the later written ones first
+        ret.extend(LogItemList::from_log_file(&file_path)?.into_iter().rev());
+    }
+
+    ret.sort_by(|a, b| b.date_time().cmp(a.date_time()));
+    ret.truncate(count);
+    Ok(ret)
+}
+
+/// Writes the log items to `output`, coloring the dates if `output` is a terminal.
+pub fn write_recent_log_items(
+    output: &mut Output,
+    log_items: &[LogItem],
+    theme: &Theme,
+) -> std::io::Result<()> {
+    for log_item in log_items {
+        if output.is_terminal() {
+            let date_str = format!("[{}]", log_item.date_time().format("%Y-%m-%d %H:%M"));
+            writeln!(
+                output,
+                "{} {}",
+                date_str.with(theme.timestamp),
+                log_item.content()
+            )?;
+        } else {
+            write!(output, "{}", log_item)?;
+        }
+    }
+    output.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use super::recent_log_items;
+
+    #[test]
+    fn test_recent_log_items() {
+        let log_dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            log_dir.path().join("2024-05-01.log"),
+            "[2024-05-01 08:00] first\n[2024-05-01 09:00] second\n",
+        )
+        .unwrap();
+        std::fs::write(
+            log_dir.path().join("2024-05-02.log"),
+            "[2024-05-02 08:00] third\n",
+        )
+        .unwrap();
+
+        let contents = |count: usize| -> Vec<String> {
+            recent_log_items(log_dir.path(), count)
+                .unwrap()
+                .iter()
+                .map(|log_item| log_item.content().to_owned())
+                .collect()
+        };
+        assert_eq!(contents(2), ["third", "second"]);
+        assert_eq!(contents(10), ["third", "second", "first"]);
+        assert!(contents(0).is_empty());
+
+        // A burst of log items with the same date time
+        std::fs::write(
+            log_dir.path().join("2024-05-03.log"),
+            "[2024-05-03 08:00] burst 1\n[2024-05-03 08:00] burst 2\n[2024-05-03 08:00] burst 3\n",
+        )
+        .unwrap();
+        assert_eq!(contents(4), ["burst 3", "burst 2", "burst 1", "third"]);
+    }
+}
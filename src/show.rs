@@ -0,0 +1,107 @@
+use std::path::Path;
+
+use crate::{config::entries, error::Error, log_item::LogItem};
+
+/// Returns the log items in `log_dir_path` whose ID starts with `id`, ignoring the case
+/// and the white space around it. More than one log item is returned if `id` is a prefix
+/// of several IDs, or if their IDs collide. An empty `id` is rejected, since every ID
+/// would match it.
+pub fn find_log_items_by_id(log_dir_path: &Path, id: &str) -> Result<Vec<LogItem>, Error> {
+    let id = id.trim().to_ascii_lowercase();
+    if id.is_empty() {
+        return Err(Error::EmptyLogItemId);
+    }
+    let mut ret: Vec<LogItem> = Vec::new();
+    for log_item in entries(log_dir_path) {
+        let log_item = log_item.map_err(Error::Io)?;
+        if log_item.id().starts_with(&id) {
+            ret.push(log_item);
+        }
+    }
+    Ok(ret)
+}
+
+/// Returns the log item with the latest date time in `log_dir_path`, or the last one
+/// in the log files if several have it.
+pub fn find_last_log_item(log_dir_path: &Path) -> std::io::Result<Option<LogItem>> {
+    let mut ret: Option<LogItem> = None;
+    for log_item in entries(log_dir_path) {
+        let log_item = log_item?;
+        if ret
+            .as_ref()
+            .is_none_or(|last| log_item.date_time() >= last.date_time())
+        {
+            ret = Some(log_item);
+        }
+    }
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::Error;
+
+    use super::{find_last_log_item, find_log_items_by_id};
+
+    #[test]
+    fn test_find_log_items_by_id() {
+        let log_dir = tempfile::tempdir().unwrap();
+        // With more log items than hex digits, some IDs share the first digit
+        let content: String = (0..17)
+            .map(|i| format!("[2024-05-01 08:{:02}] entry {}\n", i, i))
+            .collect();
+        std::fs::write(log_dir.path().join("2024-05-01.log"), content).unwrap();
+        let ids: Vec<String> = crate::config::entries(log_dir.path())
+            .map(|log_item| log_item.unwrap().id())
+            .collect();
+
+        // A full ID, or a prefix of it in any case with white space around it
+        let found = find_log_items_by_id(log_dir.path(), &ids[3]).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].content(), "entry 3");
+        let prefix = format!(" {} ", ids[3][..6].to_ascii_uppercase());
+        let found = find_log_items_by_id(log_dir.path(), &prefix).unwrap();
+        assert!(found.iter().any(|log_item| log_item.content() == "entry 3"));
+        assert!(find_log_items_by_id(log_dir.path(), "not an id")
+            .unwrap()
+            .is_empty());
+
+        // An ambiguous prefix returns all log items whose IDs start with it
+        let prefix = ids
+            .iter()
+            .map(|id| &id[..1])
+            .find(|prefix| ids.iter().filter(|id| id.starts_with(prefix)).count() > 1)
+            .unwrap();
+        let found = find_log_items_by_id(log_dir.path(), prefix).unwrap();
+        assert!(found.len() > 1);
+        assert!(found
+            .iter()
+            .all(|log_item| log_item.id().starts_with(prefix)));
+
+        for id in ["", "  "] {
+            assert!(matches!(
+                find_log_items_by_id(log_dir.path(), id),
+                Err(Error::EmptyLogItemId)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_find_last_log_item() {
+        let log_dir = tempfile::tempdir().unwrap();
+        assert!(find_last_log_item(log_dir.path()).unwrap().is_none());
+
+        std::fs::write(
+            log_dir.path().join("2024-05-01.log"),
+            "[2024-05-01 08:00] first\n",
+        )
+        .unwrap();
+        std::fs::write(
+            log_dir.path().join("2024-05-02.log"),
+            "[2024-05-02 10:00] third\n[2024-05-02 09:00] second\n[2024-05-02 10:00] fourth\n",
+        )
+        .unwrap();
+        let log_item = find_last_log_item(log_dir.path()).unwrap().unwrap();
+        assert_eq!(log_item.content(), "fourth");
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use chrono::{Datelike, Weekday};
+use serde::Serialize;
+
+use crate::cli::StatsGroupBy;
+use crate::config::read_all_log_item_lists;
+use crate::error::Error;
+use crate::log_item::LogItem;
+
+/// Counts the log items in each group. The groups are in chronological order,
+/// and every day of the week is listed when grouping by weekday.
+pub fn count_log_items_by<'a>(
+    log_items: impl IntoIterator<Item = &'a LogItem>,
+    group_by: StatsGroupBy,
+) -> Vec<(String, usize)> {
+    match group_by {
+        StatsGroupBy::Weekday => {
+            let mut counts = [0; 7];
+            for log_item in log_items {
+                counts[log_item.date_time().weekday().num_days_from_monday() as usize] += 1;
+            }
+            let mut weekday = Weekday::Mon;
+            counts
+                .into_iter()
+                .map(|count| {
+                    let ret = (weekday.to_string(), count);
+                    weekday = weekday.succ();
+                    ret
+                })
+                .collect()
+        }
+        StatsGroupBy::Week | StatsGroupBy::Month => {
+            let format = if group_by == StatsGroupBy::Week {
+                "%G-W%V"
+            } else {
+                "%Y-%m"
+            };
+            let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+            for log_item in log_items {
+                *counts
+                    .entry(log_item.date_time().format(format).to_string())
+                    .or_default() += 1;
+            }
+            counts.into_iter().collect()
+        }
+    }
+}
+
+/// Formats `rows` as a table with a left-aligned group column and a right-aligned count column.
+pub fn format_table(group_header: &str, rows: &[(String, usize)]) -> String {
+    let count_header = "Entries";
+    let group_width = rows
+        .iter()
+        .map(|(group, _)| group.chars().count())
+        .chain(std::iter::once(group_header.len()))
+        .max()
+        .unwrap_or_default();
+    let count_width = rows
+        .iter()
+        .map(|(_, count)| count.to_string().len())
+        .chain(std::iter::once(count_header.len()))
+        .max()
+        .unwrap_or_default();
+
+    let mut ret = format!(
+        "{:<group_width$}  {:>count_width$}\n",
+        group_header, count_header
+    );
+    for (group, count) in rows {
+        ret += &format!("{:<group_width$}  {:>count_width$}\n", group, count);
+    }
+    ret
+}
+
+/// The stats without grouping, as printed by `stats --json`.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct StatsSummary {
+    pub entries: usize,
+    pub days: usize,
+}
+
+/// The count of a group, as printed by `stats --by <group> --json`.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct GroupCount {
+    pub group: String,
+    pub entries: usize,
+}
+
+impl From<(String, usize)> for GroupCount {
+    fn from((group, entries): (String, usize)) -> Self {
+        Self { group, entries }
+    }
+}
+
+/// Prints the stats of the log messages, as JSON if `json` is true.
+/// The log items without content are left out if `hide_empty` is true,
+/// and today's if `exclude_today` is true.
+pub fn print_stats(
+    log_dir_path: &Path,
+    group_by: Option<StatsGroupBy>,
+    json: bool,
+    hide_empty: bool,
+    exclude_today: bool,
+) -> Result<(), Error> {
+    if !log_dir_path.exists() {
+        return Err(Error::LogDirNotFound(log_dir_path.to_path_buf()));
+    }
+
+    let mut log_item_lists =
+        read_all_log_item_lists(log_dir_path, exclude_today).map_err(Error::Io)?;
+    if hide_empty {
+        for (_, log_item_list) in &mut log_item_lists {
+            log_item_list.remove_empty_content();
+        }
+        log_item_lists.retain(|(_, log_item_list)| !log_item_list.is_empty());
+    }
+    let log_items = log_item_lists
+        .iter()
+        .flat_map(|(_, log_item_list)| log_item_list.iter());
+
+    match group_by {
+        Some(group_by) => {
+            let group_header = match group_by {
+                StatsGroupBy::Weekday => "Weekday",
+                StatsGroupBy::Week => "Week",
+                StatsGroupBy::Month => "Month",
+            };
+            let rows = count_log_items_by(log_items, group_by);
+            if json {
+                let group_counts: Vec<GroupCount> =
+                    rows.into_iter().map(GroupCount::from).collect();
+                println!(
+                    "{}",
+                    serde_json::to_string(&group_counts).expect("should serialize")
+                );
+            } else {
+                print!("{}", format_table(group_header, &rows));
+            }
+        }
+        None => {
+            let summary = StatsSummary {
+                entries: log_items.count(),
+                days: log_item_lists.len(),
+            };
+            if json {
+                println!(
+                    "{}",
+                    serde_json::to_string(&summary).expect("should serialize")
+                );
+            } else {
+                println!("Entries: {}", summary.entries);
+                println!("Days: {}", summary.days);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDateTime;
+
+    use super::{count_log_items_by, format_table, GroupCount, StatsSummary};
+    use crate::{cli::StatsGroupBy, log_item::LogItem};
+
+    fn log_items() -> Vec<LogItem> {
+        // 2024-12-30 is a Monday in the ISO week 2025-W01
+        [
+            "2024-12-30 08:00",
+            "2024-12-31 09:00",
+            "2025-01-06 10:00",
+            "2025-01-12 23:59",
+        ]
+        .map(|x| {
+            let date_time = NaiveDateTime::parse_from_str(x, "%Y-%m-%d %H:%M").unwrap();
+            LogItem::new(date_time, "content")
+        })
+        .into()
+    }
+
+    fn rows(rows: &[(&str, usize)]) -> Vec<(String, usize)> {
+        rows.iter()
+            .map(|(group, count)| (group.to_string(), *count))
+            .collect()
+    }
+
+    #[test]
+    fn test_count_log_items_by() {
+        let log_items = log_items();
+        assert_eq!(
+            count_log_items_by(&log_items, StatsGroupBy::Weekday),
+            rows(&[
+                ("Mon", 2),
+                ("Tue", 1),
+                ("Wed", 0),
+                ("Thu", 0),
+                ("Fri", 0),
+                ("Sat", 0),
+                ("Sun", 1)
+            ])
+        );
+        assert_eq!(
+            count_log_items_by(&log_items, StatsGroupBy::Week),
+            rows(&[("2025-W01", 2), ("2025-W02", 2)])
+        );
+        assert_eq!(
+            count_log_items_by(&log_items, StatsGroupBy::Month),
+            rows(&[("2024-12", 2), ("2025-01", 2)])
+        );
+        assert!(count_log_items_by(&[], StatsGroupBy::Month).is_empty());
+    }
+
+    #[test]
+    fn test_format_table() {
+        assert_eq!(
+            format_table("Month", &rows(&[("2024-12", 2), ("2025-01", 1234567890)])),
+            "Month       Entries\n2024-12           2\n2025-01  1234567890\n"
+        );
+    }
+
+    #[test]
+    fn test_stats_json() {
+        let summary = StatsSummary {
+            entries: 4,
+            days: 3,
+        };
+        assert_eq!(
+            serde_json::to_string(&summary).unwrap(),
+            r#"{"entries":4,"days":3}"#
+        );
+
+        let group_counts: Vec<GroupCount> = count_log_items_by(&log_items(), StatsGroupBy::Month)
+            .into_iter()
+            .map(GroupCount::from)
+            .collect();
+        assert_eq!(
+            serde_json::to_string(&group_counts).unwrap(),
+            r#"[{"group":"2024-12","entries":2},{"group":"2025-01","entries":2}]"#
+        );
+    }
+}
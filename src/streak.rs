@@ -0,0 +1,159 @@
+use std::path::Path;
+
+use chrono::{Days, NaiveDate};
+use serde::Serialize;
+
+use crate::{config::read_all_log_item_lists, error::Error, utils::time::get_today_date};
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Streaks {
+    /// The number of consecutive days with log messages up to today. If nothing
+    /// is written today yet, the streak up to yesterday is still current.
+    pub current: usize,
+    /// The largest number of consecutive days with log messages.
+    pub longest: usize,
+}
+
+/// Counts the streaks of `dates`, which are the dates with log messages in ascending order.
+pub fn count_streaks(dates: &[NaiveDate], today: NaiveDate) -> Streaks {
+    let mut longest: usize = 0;
+    let mut streak: usize = 0;
+    let mut prev_date: Option<NaiveDate> = None;
+    for &date in dates {
+        let is_consecutive = prev_date.is_some_and(|prev_date| prev_date + Days::new(1) == date);
+        streak = if is_consecutive { streak + 1 } else { 1 };
+        longest = longest.max(streak);
+        prev_date = Some(date);
+    }
+
+    // `streak` is the one ending at the last date, which is current if it's today or yesterday
+    let yesterday = today - Days::new(1);
+    let current = match prev_date {
+        Some(last_date) if last_date == today || last_date == yesterday => streak,
+        _ => 0,
+    };
+    Streaks { current, longest }
+}
+
+fn format_days(days: usize) -> String {
+    if days == 1 {
+        "1 day".to_owned()
+    } else {
+        format!("{} days", days)
+    }
+}
+
+/// Prints the streaks, as JSON if `json` is true.
+/// The log items without content don't count if `hide_empty` is true,
+/// and today's don't if `exclude_today` is true.
+pub fn print_streaks(
+    log_dir_path: &Path,
+    json: bool,
+    hide_empty: bool,
+    exclude_today: bool,
+) -> Result<(), Error> {
+    if !log_dir_path.exists() {
+        return Err(Error::LogDirNotFound(log_dir_path.to_path_buf()));
+    }
+
+    // A day with an empty log file doesn't count
+    let dates: Vec<NaiveDate> = read_all_log_item_lists(log_dir_path, exclude_today)
+        .map_err(Error::Io)?
+        .into_iter()
+        .filter(|(_, log_item_list)| {
+            log_item_list
+                .iter()
+                .any(|log_item| !hide_empty || !log_item.is_content_empty())
+        })
+        .map(|(date, _)| date)
+        .collect();
+    let streaks = count_streaks(&dates, get_today_date());
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string(&streaks).expect("should serialize")
+        );
+        return Ok(());
+    }
+    println!("Current streak: {}", format_days(streaks.current));
+    println!("Longest streak: {}", format_days(streaks.longest));
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+
+    use super::{count_streaks, Streaks};
+
+    fn dates(dates: &[&str]) -> Vec<NaiveDate> {
+        dates
+            .iter()
+            .map(|x| NaiveDate::parse_from_str(x, "%Y-%m-%d").unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_count_streaks() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        let streaks = |x: &[&str]| count_streaks(&dates(x), today);
+
+        assert_eq!(
+            streaks(&[]),
+            Streaks {
+                current: 0,
+                longest: 0
+            }
+        );
+        assert_eq!(
+            streaks(&["2025-03-01"]),
+            Streaks {
+                current: 1,
+                longest: 1
+            }
+        );
+        // Gaps break the streaks, and the month boundary doesn't
+        assert_eq!(
+            streaks(&[
+                "2025-01-01",
+                "2025-01-02",
+                "2025-01-03",
+                "2025-01-05",
+                "2025-02-27",
+                "2025-02-28",
+                "2025-03-01"
+            ]),
+            Streaks {
+                current: 3,
+                longest: 3
+            }
+        );
+        // Nothing written today yet
+        assert_eq!(
+            streaks(&["2025-02-27", "2025-02-28"]),
+            Streaks {
+                current: 2,
+                longest: 2
+            }
+        );
+        assert_eq!(
+            streaks(&["2025-01-01", "2025-01-02", "2025-02-27"]),
+            Streaks {
+                current: 0,
+                longest: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_streaks_json() {
+        let streaks = Streaks {
+            current: 2,
+            longest: 5,
+        };
+        assert_eq!(
+            serde_json::to_string(&streaks).unwrap(),
+            r#"{"current":2,"longest":5}"#
+        );
+    }
+}
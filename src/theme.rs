@@ -0,0 +1,88 @@
+use crossterm::style::Color;
+
+use crate::config::ThemeConfig;
+
+/// The colors of the output, resolved from the `[theme]` section of the config.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    /// The foreground of the date and time of the log items
+    pub timestamp: Color,
+    /// The foreground of the date line of the single-date pager and the day separators
+    /// of the all-pager
+    pub date_line: Color,
+    /// The background of the search results
+    pub search_match: Color,
+    /// The background of the error messages in the pagers
+    pub error: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            timestamp: Color::Green,
+            date_line: Color::DarkGrey,
+            search_match: Color::White,
+            error: Color::Red,
+        }
+    }
+}
+
+/// Parses a color name, such as `green` or `dark_grey`.
+pub fn parse_color(name: &str) -> Option<Color> {
+    Color::try_from(name).ok()
+}
+
+impl Theme {
+    /// Resolves the color names in `theme_config`. An unknown color name is replaced by
+    /// the default color with a warning.
+    pub fn from_config(theme_config: &ThemeConfig) -> Self {
+        let default = Self::default();
+        let resolve = |key: &str, name: &str, default_color: Color| {
+            parse_color(name).unwrap_or_else(|| {
+                eprintln!(
+                    "warning: unknown color `{}` for `theme.{}`, using the default one",
+                    name, key
+                );
+                default_color
+            })
+        };
+        Self {
+            timestamp: resolve("timestamp", &theme_config.timestamp, default.timestamp),
+            date_line: resolve("date_line", &theme_config.date_line, default.date_line),
+            search_match: resolve(
+                "search_match",
+                &theme_config.search_match,
+                default.search_match,
+            ),
+            error: resolve("error", &theme_config.error, default.error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crossterm::style::Color;
+
+    use crate::config::ThemeConfig;
+
+    use super::{parse_color, Theme};
+
+    #[test]
+    fn test_theme_from_config() {
+        assert_eq!(
+            Theme::from_config(&ThemeConfig::default()),
+            Theme::default()
+        );
+
+        let theme_config = ThemeConfig {
+            timestamp: "Dark_Cyan".to_owned(),
+            search_match: "no such color".to_owned(),
+            ..Default::default()
+        };
+        let theme = Theme::from_config(&theme_config);
+        assert_eq!(theme.timestamp, Color::DarkCyan);
+        assert_eq!(theme.search_match, Theme::default().search_match);
+        assert_eq!(parse_color("dark_grey"), Some(Color::DarkGrey));
+        assert_eq!(parse_color("grey50"), None);
+    }
+}
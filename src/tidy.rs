@@ -0,0 +1,269 @@
+use std::{fs::File, io::Read, path::Path, str::FromStr};
+
+use chrono::NaiveDate;
+
+use crate::{
+    config::{construct_log_file_path, get_all_log_dates},
+    error::Error,
+    log_item::{LogItem, LogItemList},
+    utils::fs::{open_locked, overwrite_file},
+};
+
+/// Parses `content`, or returns `None` if it has text before the first log item,
+/// which would be lost by rewriting the log file.
+pub fn parse_log_content(content: &str) -> Option<LogItemList> {
+    let first_line = content.lines().find(|line| !line.trim().is_empty());
+    if first_line.is_some_and(|line| LogItem::from_str(line).is_err()) {
+        return None;
+    }
+    Some(LogItemList::from_str(content).expect("Invalid log file"))
+}
+
+/// Returns `content` with its log items sorted by date time in the canonical format,
+/// or `None` if it has text before the first log item.
+pub fn tidy_log_content(content: &str) -> Option<String> {
+    let mut log_item_list = parse_log_content(content)?;
+    log_item_list.sort();
+    Some(log_item_list.to_string())
+}
+
+/// Returns `content` without the log items equal to the one before them and the number
+/// of the removed log items, or `None` if it has text before the first log item.
+/// `content` is returned unchanged if nothing is removed.
+pub fn dedupe_log_content(content: &str) -> Option<(String, usize)> {
+    let mut log_item_list = parse_log_content(content)?;
+    match log_item_list.dedup() {
+        0 => Some((content.to_owned(), 0)),
+        removed_count => Some((log_item_list.to_string(), removed_count)),
+    }
+}
+
+/// Reads the log file at `file_path`. Unless `dry_run` is true, it's opened by `open_locked`
+/// and returned too, so that it's rewritten before any concurrent write is appended.
+fn read_log_file(file_path: &Path, dry_run: bool) -> Result<(Option<File>, String), Error> {
+    if dry_run {
+        let content =
+            std::fs::read_to_string(file_path).map_err(|error| Error::from_io(error, file_path))?;
+        return Ok((None, content));
+    }
+    let mut file = open_locked(file_path).map_err(|error| Error::from_io(error, file_path))?;
+    let mut content = String::new();
+    file.read_to_string(&mut content)
+        .map_err(|error| Error::from_io(error, file_path))?;
+    Ok((Some(file), content))
+}
+
+/// Tidies the log files of `dates` and prints the changed ones. Nothing is written if
+/// `dry_run` is true, and nothing is printed if `quiet` is true unless `dry_run` is true too.
+pub fn tidy_log_files(
+    log_dir_path: &Path,
+    dates: &[NaiveDate],
+    dry_run: bool,
+    quiet: bool,
+) -> Result<(), Error> {
+    let quiet = quiet && !dry_run;
+    let mut changed_count: usize = 0;
+    for date in dates {
+        let file_path = construct_log_file_path(log_dir_path, date);
+        let (file, content) = read_log_file(&file_path, dry_run)?;
+        let Some(tidied_content) = tidy_log_content(&content) else {
+            warn_leading_text(&file_path);
+            continue;
+        };
+        if tidied_content == content {
+            continue;
+        }
+
+        changed_count += 1;
+        match file {
+            None => println!("Would tidy '{}'", file_path.display()),
+            Some(mut file) => {
+                overwrite_file(&mut file, &tidied_content)
+                    .map_err(|error| Error::from_io(error, &file_path))?;
+                if !quiet {
+                    println!("Tidied '{}'", file_path.display());
+                }
+            }
+        }
+    }
+
+    if changed_count == 0 && !quiet {
+        println!("Nothing to tidy.");
+    }
+    Ok(())
+}
+
+/// Warns that the log file is skipped because rewriting it would lose the text before
+/// the first log item.
+pub fn warn_leading_text(file_path: &Path) {
+    eprintln!(
+        "warning: skipped '{}', which has text before the first log message",
+        file_path.display()
+    );
+}
+
+/// Removes the duplicate log items of the log files of `dates` and prints how many are
+/// removed. Nothing is written if `dry_run` is true, and nothing is printed if `quiet`
+/// is true unless `dry_run` is true too.
+pub fn dedupe_log_files(
+    log_dir_path: &Path,
+    dates: &[NaiveDate],
+    dry_run: bool,
+    quiet: bool,
+) -> Result<(), Error> {
+    let quiet = quiet && !dry_run;
+    let mut total_removed_count: usize = 0;
+    for date in dates {
+        let file_path = construct_log_file_path(log_dir_path, date);
+        let (file, content) = read_log_file(&file_path, dry_run)?;
+        let Some((deduped_content, removed_count)) = dedupe_log_content(&content) else {
+            warn_leading_text(&file_path);
+            continue;
+        };
+        if removed_count == 0 {
+            continue;
+        }
+
+        total_removed_count += removed_count;
+        match file {
+            None => println!(
+                "Would remove {} from '{}'",
+                format_duplicates(removed_count),
+                file_path.display()
+            ),
+            Some(mut file) => {
+                overwrite_file(&mut file, &deduped_content)
+                    .map_err(|error| Error::from_io(error, &file_path))?;
+                if !quiet {
+                    println!(
+                        "Removed {} from '{}'",
+                        format_duplicates(removed_count),
+                        file_path.display()
+                    );
+                }
+            }
+        }
+    }
+
+    if total_removed_count == 0 && !quiet {
+        println!("No duplicate found.");
+    }
+    Ok(())
+}
+
+fn format_duplicates(count: usize) -> String {
+    if count == 1 {
+        "1 duplicate".to_owned()
+    } else {
+        format!("{} duplicates", count)
+    }
+}
+
+/// Returns the dates to tidy or dedupe: all dates with a log file if `all` is true,
+/// otherwise `date` if its log file exists.
+pub fn selected_log_dates(
+    log_dir_path: &Path,
+    date: NaiveDate,
+    all: bool,
+) -> Result<Vec<NaiveDate>, Error> {
+    if all {
+        return get_all_log_dates(log_dir_path).map_err(Error::Io);
+    }
+    if construct_log_file_path(log_dir_path, &date).exists() {
+        Ok(vec![date])
+    } else {
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+
+    use super::{dedupe_log_content, dedupe_log_files, tidy_log_content, tidy_log_files};
+
+    #[test]
+    fn test_tidy_log_files() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 5, 12).unwrap();
+        let file_path = log_dir.path().join("2024-05-12.log");
+        let content = "[2024-05-12 09:00] second\n\n\n[2024-5-12 08:00]   first\n\n\n";
+        std::fs::write(&file_path, content).unwrap();
+
+        tidy_log_files(log_dir.path(), &[date], true, true).unwrap();
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), content);
+        // The rewritten log file is shorter, and nothing is left of the old content
+        tidy_log_files(log_dir.path(), &[date], false, true).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&file_path).unwrap(),
+            "[2024-05-12 08:00] first\n[2024-05-12 09:00] second\n"
+        );
+    }
+
+    #[test]
+    fn test_dedupe_log_files() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 5, 12).unwrap();
+        let file_path = log_dir.path().join("2024-05-12.log");
+        let content = "[2024-05-12 08:00] first\n[2024-05-12 08:00] first\n";
+        std::fs::write(&file_path, content).unwrap();
+
+        dedupe_log_files(log_dir.path(), &[date], true, true).unwrap();
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), content);
+        dedupe_log_files(log_dir.path(), &[date], false, true).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&file_path).unwrap(),
+            "[2024-05-12 08:00] first\n"
+        );
+    }
+
+    #[test]
+    fn test_tidy_log_content() {
+        let content = "[2024-05-12 09:00] second\nline\n[2024-5-12 08:00]   first\n\n";
+        let tidied_content = tidy_log_content(content).unwrap();
+        assert_eq!(
+            tidied_content,
+            "[2024-05-12 08:00] first\n[2024-05-12 09:00] second\nline\n"
+        );
+        // Tidying twice changes nothing
+        assert_eq!(tidy_log_content(&tidied_content).unwrap(), tidied_content);
+
+        assert_eq!(tidy_log_content("").unwrap(), "");
+        assert!(tidy_log_content("note\n[2024-05-12 08:00] first\n").is_none());
+    }
+
+    #[test]
+    fn test_dedupe_log_content() {
+        // Exact duplicates, also of multi-line log items
+        let content = "[2024-05-12 08:00] first\n\
+                       [2024-05-12 08:00] first\n\
+                       [2024-05-12 09:00] second\nline\n\
+                       [2024-05-12 09:00] second\nline\n\
+                       [2024-05-12 09:00] second\nline\n";
+        assert_eq!(
+            dedupe_log_content(content).unwrap(),
+            (
+                "[2024-05-12 08:00] first\n[2024-05-12 09:00] second\nline\n".to_owned(),
+                3
+            )
+        );
+
+        // Near-duplicates and non-adjacent duplicates are kept
+        let content = "[2024-05-12 08:00] first\n\
+                       [2024-05-12 08:01] first\n\
+                       [2024-05-12 08:01] First\n\
+                       [2024-05-12 08:00] first\n";
+        assert_eq!(
+            dedupe_log_content(content).unwrap(),
+            (content.to_owned(), 0)
+        );
+
+        // Nothing is changed without duplicates, even if it's not canonical
+        let content = "[2024-5-12 08:00]   first\n\n";
+        assert_eq!(
+            dedupe_log_content(content).unwrap(),
+            (content.to_owned(), 0)
+        );
+        assert!(dedupe_log_content("note\n[2024-05-12 08:00] first\n").is_none());
+    }
+}
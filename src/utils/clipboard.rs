@@ -0,0 +1,15 @@
+/// Copies `text` to the system clipboard, creating `clipboard` first if it's `None`.
+/// Fails instead of panicking if there is no clipboard, e.g. in a headless environment.
+///
+/// On Linux, the copied text is only served while the clipboard is alive, unless a
+/// clipboard manager takes it over, so `clipboard` should be kept rather than dropped.
+pub fn copy_to_clipboard(
+    clipboard: &mut Option<arboard::Clipboard>,
+    text: &str,
+) -> Result<(), arboard::Error> {
+    let clipboard = match clipboard {
+        Some(clipboard) => clipboard,
+        None => clipboard.insert(arboard::Clipboard::new()?),
+    };
+    clipboard.set_text(text)
+}
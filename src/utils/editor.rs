@@ -0,0 +1,166 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::Error;
+
+/// Returns the path of `program`, which is looked up in `$PATH` unless it's a path itself.
+fn find_program(program: &str) -> Option<PathBuf> {
+    let program_path = Path::new(program);
+    if program_path.components().count() > 1 {
+        return program_path.is_file().then(|| program_path.to_path_buf());
+    }
+    let candidates = |dir: PathBuf| {
+        let file_path = dir.join(program);
+        // Windows runs `notepad` as `notepad.exe`
+        let exe_file_path = cfg!(windows).then(|| file_path.with_extension("exe"));
+        std::iter::once(file_path).chain(exe_file_path)
+    };
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .flat_map(candidates)
+        .find(|file_path| file_path.is_file())
+}
+
+/// Returns the editor used to write and edit the logs, which is `editor` (`log.editor`)
+/// if it isn't empty, or the one determined by `$VISUAL` or `$EDITOR`.
+/// The program of `editor` must be found, so that a typo is reported before editing.
+pub fn find_editor(editor: &str) -> Result<String, Error> {
+    if !editor.trim().is_empty() {
+        let invalid_editor = || Error::InvalidValue {
+            key: "log.editor".to_owned(),
+            value: editor.to_owned(),
+        };
+        let words = split_command_line(editor).ok_or_else(invalid_editor)?;
+        let program = words.first().ok_or_else(invalid_editor)?;
+        if find_program(program).is_none() {
+            return Err(Error::EditorNotFound(program.to_owned()));
+        }
+        return Ok(editor.to_owned());
+    }
+    edit::get_editor()
+        .map(|editor_path| editor_path.display().to_string())
+        .map_err(|_| Error::NoEditor)
+}
+
+/// Splits `command_line` into words like a shell, where quotes group the words and
+/// a backslash escapes the next character outside single quotes.
+/// Returns `None` if a quote isn't closed.
+pub fn split_command_line(command_line: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut quote: Option<char> = None;
+    let mut chars = command_line.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('\''), c) => word.get_or_insert_default().push(c),
+            (_, '\\') => word.get_or_insert_default().push(chars.next()?),
+            (Some(_), c) => word.get_or_insert_default().push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                word.get_or_insert_default();
+            }
+            (None, c) if c.is_whitespace() => words.extend(word.take()),
+            (None, c) => word.get_or_insert_default().push(c),
+        }
+    }
+    if quote.is_some() {
+        return None;
+    }
+    words.extend(word);
+    Some(words)
+}
+
+/// Opens `file_path` in `editor` and waits for it to exit. `editor` is a command line
+/// that may have arguments, e.g. `nvim -c 'set ft=markdown'`, and the file path is
+/// appended to it. If `editor` is empty, the editor of `$VISUAL` or `$EDITOR` is used.
+pub fn edit_file(file_path: &Path, editor: &str) -> std::io::Result<()> {
+    if editor.trim().is_empty() {
+        return edit::edit_file(file_path);
+    }
+    let invalid_editor = || {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("invalid `log.editor`: `{}`", editor),
+        )
+    };
+    let words = split_command_line(editor).ok_or_else(invalid_editor)?;
+    let (program, args) = words.split_first().ok_or_else(invalid_editor)?;
+    let status = Command::new(program)
+        .args(args)
+        .arg(file_path)
+        .status()
+        .map_err(|error| {
+            std::io::Error::new(
+                error.kind(),
+                format!("Unable to run `{}`: {}", program, error),
+            )
+        })?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!(
+            "`{}` failed with {}",
+            editor, status
+        )))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::Error;
+
+    use super::{edit_file, find_editor, split_command_line};
+
+    #[test]
+    fn test_split_command_line() {
+        assert_eq!(
+            split_command_line("nvim -c 'set ft=markdown'").unwrap(),
+            ["nvim", "-c", "set ft=markdown"]
+        );
+        assert_eq!(
+            split_command_line(r#"  "my editor"  --flag="a b" x\ y '' "#).unwrap(),
+            ["my editor", "--flag=a b", "x y", ""]
+        );
+        assert_eq!(
+            split_command_line(r#"code "it's" 'a\b'"#).unwrap(),
+            ["code", "it's", r"a\b"]
+        );
+        assert_eq!(split_command_line("").unwrap(), Vec::<String>::new());
+        assert!(split_command_line("vim 'unclosed").is_none());
+        assert!(split_command_line("vim \\").is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_editor() {
+        assert_eq!(find_editor("sh -c 'exit 0'").unwrap(), "sh -c 'exit 0'");
+        assert_eq!(find_editor("/bin/sh").unwrap(), "/bin/sh");
+        assert!(matches!(
+            find_editor("no-such-editor --wait"),
+            Err(Error::EditorNotFound(program)) if program == "no-such-editor"
+        ));
+        assert!(matches!(
+            find_editor("/no/such/editor"),
+            Err(Error::EditorNotFound(_))
+        ));
+        assert!(matches!(
+            find_editor("vim 'unclosed"),
+            Err(Error::InvalidValue { .. })
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_edit_file_with_args() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = dir.path().join("log.txt");
+        std::fs::write(&file_path, "old\n").unwrap();
+
+        // `sh -c 'script' name file` runs the script with the file as `$1`
+        edit_file(&file_path, "sh -c 'echo \"new $0\" > \"$1\"' 'arg'").unwrap();
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "new arg\n");
+
+        assert!(edit_file(&file_path, "sh -c 'exit 1'").is_err());
+        assert!(edit_file(&file_path, "vim 'unclosed").is_err());
+    }
+}
@@ -0,0 +1,36 @@
+use std::{path::Path, process::Command};
+
+/// The program that opens a directory in the system's file manager.
+fn file_manager_program() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else {
+        "xdg-open"
+    }
+}
+
+/// Opens the directory at `dir_path` in the system's file manager.
+pub fn open_in_file_manager(dir_path: &Path) -> std::io::Result<()> {
+    let program = file_manager_program();
+    let status = Command::new(program)
+        .arg(dir_path)
+        .status()
+        .map_err(|error| {
+            std::io::Error::new(
+                error.kind(),
+                format!("Unable to run `{}`: {}", program, error),
+            )
+        })?;
+    // `explorer` exits with 1 even if the directory is opened
+    if status.success() || cfg!(target_os = "windows") {
+        return Ok(());
+    }
+    Err(std::io::Error::other(format!(
+        "`{} {}` failed with {}",
+        program,
+        dir_path.display(),
+        status
+    )))
+}
@@ -1,6 +1,6 @@
 use std::{
     fs::{File, OpenOptions},
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 use crate::constants::PKG_NAME;
@@ -29,15 +29,78 @@ pub fn create_unique_temp_file() -> (File, PathBuf) {
     unreachable!("Ran out of unique temporary file names");
 }
 
+/// Appends `s` to the file at `file_path`, creating it if it doesn't exist.
+/// The file is locked exclusively while writing, so that concurrent appends,
+/// e.g. from `write` in two shells, don't interleave.
 pub fn append_str_to_file(file_path: &PathBuf, s: &str) -> std::io::Result<usize> {
     use std::fs::OpenOptions;
     use std::io;
 
     let mut file = OpenOptions::new()
+        .create(true) // Create the file if it doesn't exist
         .append(true) // Enable appending
         .open(file_path)?; // Open the file
 
-    io::Write::write(&mut file, s.as_bytes()) // Write the line with a newline at the end
+    file.lock()?;
+    let result = io::Write::write_all(&mut file, s.as_bytes());
+    // Release the lock right away instead of when the file is closed
+    file.unlock()?;
+    result.map(|_| s.len())
+}
+
+/// Opens the file at `file_path` for reading and rewriting it, creating it if it doesn't exist.
+/// The file is locked exclusively until it's closed, so that no message is appended by
+/// `append_str_to_file` between reading and rewriting it.
+pub fn open_locked(file_path: &Path) -> std::io::Result<File> {
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(file_path)?;
+    file.lock()?;
+    Ok(file)
+}
+
+/// Replaces the content of `file`, e.g. one opened by `open_locked`, with `s`.
+pub fn overwrite_file(file: &mut File, s: &str) -> std::io::Result<()> {
+    use std::io::{Seek, Write};
+
+    file.set_len(0)?;
+    file.rewind()?;
+    file.write_all(s.as_bytes())
+}
+
+/// Reads the file at `file_path` like `std::fs::read_to_string`, but replaces the invalid
+/// UTF-8 sequences with `U+FFFD` instead of failing, so that the rest can still be shown.
+/// Returns the content and whether the file was valid UTF-8.
+pub fn read_to_string_lossy(file_path: &Path) -> std::io::Result<(String, bool)> {
+    let bytes = std::fs::read(file_path)?;
+    Ok(match String::from_utf8(bytes) {
+        Ok(content) => (content, true),
+        Err(error) => (
+            String::from_utf8_lossy(error.as_bytes()).into_owned(),
+            false,
+        ),
+    })
+}
+
+/// Removes the file at `file_path` if it contains only white space characters.
+/// Returns `true` if the file has been removed. A missing file is left as is.
+///
+/// Files with any other content are kept, even if they don't contain a parseable log item,
+/// to avoid losing text that the user typed.
+pub fn remove_file_if_blank(file_path: &PathBuf) -> std::io::Result<bool> {
+    let content = match std::fs::read(file_path) {
+        Ok(content) => content,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(error) => return Err(error),
+    };
+    if !content.iter().all(u8::is_ascii_whitespace) {
+        return Ok(false);
+    }
+    std::fs::remove_file(file_path)?;
+    Ok(true)
 }
 
 #[cfg(test)]
@@ -107,4 +170,73 @@ mod test {
         let final_file_content = read_to_string(file_path).expect("Fail to read the file");
         assert_eq!(final_file_content, expected_file_content);
     }
+
+    #[test]
+    fn test_append_str_to_nonexistent_file() {
+        use super::append_str_to_file;
+
+        let (_, file_path) = super::create_unique_temp_file();
+        std::fs::remove_file(&file_path).expect("Unable to delete the file");
+        let test_config = TestAppendStrToFileConfig { file_path };
+        let file_path = &test_config.file_path;
+
+        append_str_to_file(file_path, "created").expect("Fail to write the file");
+        let final_file_content = read_to_string(file_path).expect("Fail to read the file");
+        assert_eq!(final_file_content, "created");
+    }
+
+    #[test]
+    fn test_concurrent_append_str_to_file() {
+        use super::append_str_to_file;
+        use crate::log_item::LogItemList;
+        use std::str::FromStr;
+
+        let (_, file_path) = super::create_unique_temp_file();
+        let test_config = TestAppendStrToFileConfig { file_path };
+        let file_path = &test_config.file_path;
+
+        // Long log items, so that unserialized writes would be likely to interleave
+        let threads: Vec<_> = ["a", "b"]
+            .into_iter()
+            .map(|content| {
+                let file_path = file_path.clone();
+                std::thread::spawn(move || {
+                    let log_item = format!("[2024-05-06 12:00] {}\n", content.repeat(100_000));
+                    for _ in 0..20 {
+                        append_str_to_file(&file_path, &log_item).expect("Fail to write the file");
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        let final_file_content = read_to_string(file_path).expect("Fail to read the file");
+        let log_item_list = LogItemList::from_str(&final_file_content).unwrap();
+        assert_eq!(log_item_list.len(), 40);
+        for log_item in log_item_list.iter() {
+            let content = log_item.content();
+            assert_eq!(content.len(), 100_000);
+            assert!(content == "a".repeat(100_000) || content == "b".repeat(100_000));
+        }
+    }
+
+    #[test]
+    fn test_remove_file_if_blank() {
+        use super::remove_file_if_blank;
+
+        let (_, file_path) = super::create_unique_temp_file();
+        std::fs::write(&file_path, " \n\t\n").expect("Fail to write the file");
+        assert!(remove_file_if_blank(&file_path).expect("Fail to remove the file"));
+        assert!(!file_path.exists());
+        assert!(!remove_file_if_blank(&file_path).expect("Fail to skip the missing file"));
+
+        let (_, file_path) = super::create_unique_temp_file();
+        let test_config = TestAppendStrToFileConfig { file_path };
+        std::fs::write(&test_config.file_path, "\nnot a log item\n")
+            .expect("Fail to write the file");
+        assert!(!remove_file_if_blank(&test_config.file_path).expect("Fail to read the file"));
+        assert!(test_config.file_path.exists());
+    }
 }
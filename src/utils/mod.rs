@@ -1,3 +1,8 @@
+pub mod clipboard;
+pub mod editor;
+pub mod file_manager;
 pub mod fs;
+pub mod output;
+pub mod template;
 pub mod terminal;
 pub mod time;
\ No newline at end of file
@@ -0,0 +1,141 @@
+use std::{
+    fs::File,
+    io::{IsTerminal, Write},
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use crate::{log_item::LogItem, utils::editor::split_command_line};
+
+/// The destination of a command's output: a file, or stdout if no file is given.
+pub struct Output {
+    writer: Box<dyn Write>,
+    is_terminal: bool,
+}
+
+impl Output {
+    pub fn new(file_path: Option<&Path>) -> std::io::Result<Self> {
+        Ok(match file_path {
+            Some(file_path) => Self {
+                writer: Box::new(File::create(file_path)?),
+                is_terminal: false,
+            },
+            None => Self {
+                writer: Box::new(std::io::stdout()),
+                is_terminal: std::io::stdout().is_terminal(),
+            },
+        })
+    }
+
+    /// Whether the output goes to a terminal, and thus can be colored.
+    pub fn is_terminal(&self) -> bool {
+        self.is_terminal
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+/// Prints `content` to stdout, or pipes it through `$PAGER` if it's set, for when the
+/// built-in pager can't be used because stdout isn't a terminal.
+/// A reader that stops early, as `head` does, isn't an error.
+pub fn print_or_page(content: &str) -> std::io::Result<()> {
+    let pager = std::env::var("PAGER").unwrap_or_default();
+    let result = match split_command_line(&pager).unwrap_or_default().split_first() {
+        Some((program, args)) => page(content, program, args),
+        None => {
+            let mut stdout = std::io::stdout();
+            stdout
+                .write_all(content.as_bytes())
+                .and_then(|_| stdout.flush())
+        }
+    };
+    match result {
+        Err(error) if error.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        result => result,
+    }
+}
+
+/// Runs `program` with `content` as its stdin and waits for it to exit.
+fn page(content: &str, program: &str, args: &[String]) -> std::io::Result<()> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|error| {
+            std::io::Error::new(
+                error.kind(),
+                format!("Unable to run `{}`: {}", program, error),
+            )
+        })?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // The pager may quit before reading everything, which isn't an error
+        match stdin.write_all(content.as_bytes()) {
+            Err(error) if error.kind() != std::io::ErrorKind::BrokenPipe => return Err(error),
+            _ => {}
+        }
+    }
+    child.wait()?;
+    Ok(())
+}
+
+/// Writes each log item as `template` expanded by `LogItem::expand_template`, one per line.
+pub fn write_log_items_with_template(
+    output: &mut impl Write,
+    log_items: &[LogItem],
+    template: &str,
+) -> std::io::Result<()> {
+    for log_item in log_items {
+        writeln!(output, "{}", log_item.expand_template(template))?;
+    }
+    output.flush()
+}
+
+/// Returns the number of log items and of their days, e.g. `3 entries, 2 days`.
+pub fn log_items_summary(log_items: &[LogItem]) -> String {
+    let mut dates: Vec<_> = log_items
+        .iter()
+        .map(|log_item| log_item.date_time().date())
+        .collect();
+    dates.sort();
+    dates.dedup();
+    let entries = match log_items.len() {
+        1 => "1 entry".to_owned(),
+        count => format!("{} entries", count),
+    };
+    let days = match dates.len() {
+        1 => "1 day".to_owned(),
+        count => format!("{} days", count),
+    };
+    format!("{}, {}", entries, days)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDateTime;
+
+    use crate::log_item::LogItem;
+
+    use super::log_items_summary;
+
+    #[test]
+    fn test_log_items_summary() {
+        let log_items: Vec<LogItem> = ["2024-05-01 08:00", "2024-05-01 09:00", "2024-05-03 08:00"]
+            .iter()
+            .map(|x| {
+                let date_time = NaiveDateTime::parse_from_str(x, "%Y-%m-%d %H:%M").unwrap();
+                LogItem::new(date_time, "content")
+            })
+            .collect();
+        assert_eq!(log_items_summary(&log_items), "3 entries, 2 days");
+        assert_eq!(log_items_summary(&log_items[..1]), "1 entry, 1 day");
+        assert_eq!(log_items_summary(&[]), "0 entries, 0 days");
+    }
+}
@@ -0,0 +1,49 @@
+/// Replaces each `{name}` placeholder in `template` with `lookup(name)`.
+/// Placeholders for which `lookup` returns `None` are left as they are.
+pub fn expand_template(template: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut ret = String::new();
+    let mut rest = template;
+    while let Some(begin) = rest.find('{') {
+        ret += &rest[..begin];
+        rest = &rest[begin..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        let name = &rest[1..end];
+        match lookup(name) {
+            Some(value) if !name.contains('{') => ret += &value,
+            // Keep the brace, and look for a placeholder after it
+            _ => {
+                ret.push('{');
+                rest = &rest[1..];
+                continue;
+            }
+        }
+        rest = &rest[end + 1..];
+    }
+    ret += rest;
+    ret
+}
+
+#[cfg(test)]
+mod test {
+    use super::expand_template;
+
+    #[test]
+    fn test_expand_template() {
+        let lookup = |name: &str| match name {
+            "date" => Some("2024-05-12".to_owned()),
+            "count" => Some("3".to_owned()),
+            _ => None,
+        };
+        assert_eq!(
+            expand_template("{date} ({count} entries)", lookup),
+            "2024-05-12 (3 entries)"
+        );
+        assert_eq!(
+            expand_template("{unknown} {date}{ {{count}} {", lookup),
+            "{unknown} 2024-05-12{ {3} {"
+        );
+        assert_eq!(expand_template("", lookup), "");
+    }
+}
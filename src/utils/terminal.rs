@@ -1,11 +1,75 @@
+/// The terminal size assumed when it can't be determined, e.g. without a TTY.
+pub const DEFAULT_TERMINAL_SIZE: (u16, u16) = (80, 24);
+
+/// Returns the `(cols, rows)` of `size`, or `DEFAULT_TERMINAL_SIZE` if getting the size failed.
+fn terminal_size_or_default(size: std::io::Result<(u16, u16)>) -> (u16, u16) {
+    size.unwrap_or(DEFAULT_TERMINAL_SIZE)
+}
+
 pub fn get_terminal_total_rows() -> u16 {
-    let terminal_size = crossterm::terminal::size().expect("Doesn't support get height");
-    terminal_size.1
+    terminal_size_or_default(crossterm::terminal::size()).1
 }
 
 pub fn get_terminal_total_cols() -> u16 {
-    let terminal_size = crossterm::terminal::size().expect("Doesn't support get width");
-    terminal_size.0
+    terminal_size_or_default(crossterm::terminal::size()).0
+}
+
+/// Enters raw mode and the alternate screen, and leaves them when dropped.
+///
+/// Holding the guard for the lifetime of a pager keeps the terminal usable even if
+/// the pager returns early or panics.
+pub struct TerminalGuard {
+    alternate_screen: bool,
+}
+
+impl TerminalGuard {
+    /// If `alternate_screen` is false, the pager is drawn on the normal screen
+    /// and the last page stays in the scrollback after exiting.
+    pub fn new(alternate_screen: bool) -> Result<Self, std::io::Error> {
+        crossterm::terminal::enable_raw_mode()?;
+        if alternate_screen {
+            crossterm::execute!(std::io::stdout(), crossterm::terminal::EnterAlternateScreen)?;
+        } else {
+            // Scroll the current content of the screen into the scrollback,
+            // so that it isn't overwritten by the pager.
+            let rows = get_terminal_total_rows();
+            crossterm::execute!(
+                std::io::stdout(),
+                crossterm::cursor::MoveTo(0, rows.saturating_sub(1)),
+                crossterm::style::Print("\r\n".repeat(rows as usize))
+            )?;
+        }
+        Ok(Self { alternate_screen })
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        if self.alternate_screen {
+            let _ = restore_terminal();
+        } else {
+            // Leave the last page on the screen and put the cursor below it.
+            let rows = get_terminal_total_rows();
+            let _ = crossterm::execute!(
+                std::io::stdout(),
+                crossterm::cursor::MoveTo(0, rows.saturating_sub(1)),
+                crossterm::terminal::Clear(crossterm::terminal::ClearType::CurrentLine),
+                crossterm::cursor::Show
+            );
+        }
+        let _ = crossterm::terminal::disable_raw_mode();
+    }
+}
+
+/// Restores the terminal before the default panic hook prints the panic message,
+/// so that the message isn't lost in the alternate screen.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        let _ = restore_terminal();
+        let _ = crossterm::terminal::disable_raw_mode();
+        default_hook(panic_info);
+    }));
 }
 
 pub fn restore_terminal() -> Result<(), std::io::Error> {
@@ -17,3 +81,15 @@ pub fn restore_terminal() -> Result<(), std::io::Error> {
     )?;
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use super::{terminal_size_or_default, DEFAULT_TERMINAL_SIZE};
+
+    #[test]
+    fn test_terminal_size_or_default() {
+        assert_eq!(terminal_size_or_default(Ok((120, 40))), (120, 40));
+        let error = std::io::Error::other("not a terminal");
+        assert_eq!(terminal_size_or_default(Err(error)), DEFAULT_TERMINAL_SIZE);
+    }
+}
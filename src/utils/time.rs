@@ -1,4 +1,4 @@
-use chrono::{NaiveDate, NaiveDateTime};
+use chrono::{Datelike, Days, Locale, NaiveDate, NaiveDateTime, TimeDelta};
 
 pub fn get_today_date() -> NaiveDate {
     chrono::prelude::Local::now().date_naive()
@@ -7,3 +7,204 @@ pub fn get_today_date() -> NaiveDate {
 pub fn date_time_now() -> NaiveDateTime {
     chrono::prelude::Local::now().naive_local()
 }
+
+/// Returns the abbreviated name of the weekday of `date` in `locale`, e.g. `Mon` in `en_US`.
+pub fn weekday_name(date: NaiveDate, locale: Locale) -> String {
+    date.format_localized("%a", locale).to_string()
+}
+
+/// Formats `duration` in hours and minutes, e.g. `2h 13m`, or only minutes if shorter
+/// than an hour. A negative duration is formatted as zero.
+pub fn format_duration(duration: TimeDelta) -> String {
+    let minutes = duration.num_minutes().max(0);
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    }
+}
+
+/// Formats the time from `date_time` to `now` in its largest unit, e.g. `2h ago` or `3d ago`.
+/// Less than a minute, or a `date_time` after `now`, is `just now`.
+pub fn format_time_ago(date_time: NaiveDateTime, now: NaiveDateTime) -> String {
+    let duration = now - date_time;
+    if duration.num_minutes() < 1 {
+        "just now".to_owned()
+    } else if duration.num_hours() < 1 {
+        format!("{}m ago", duration.num_minutes())
+    } else if duration.num_days() < 1 {
+        format!("{}h ago", duration.num_hours())
+    } else if duration.num_days() < 365 {
+        format!("{}d ago", duration.num_days())
+    } else {
+        format!("{}y ago", duration.num_days() / 365)
+    }
+}
+
+/// Parses `date_str` with each of `formats` in order, and returns the first successful result.
+///
+/// A format without a year, such as `%m-%d`, is completed with the current year. If
+/// `yearless_in_past` is true, a date after today is moved to the previous year instead,
+/// since a diary is mostly about the past. The keywords `today`, `yesterday` and
+/// `tomorrow` are accepted as well.
+pub fn parse_date_from_str(
+    date_str: &str,
+    formats: &[String],
+    yearless_in_past: bool,
+) -> Result<NaiveDate, chrono::ParseError> {
+    parse_date_relative_to(date_str, formats, yearless_in_past, get_today_date())
+}
+
+/// Same as `parse_date_from_str`, but relative to `today` instead of the current date.
+fn parse_date_relative_to(
+    date_str: &str,
+    formats: &[String],
+    yearless_in_past: bool,
+    today: NaiveDate,
+) -> Result<NaiveDate, chrono::ParseError> {
+    match date_str {
+        "today" => return Ok(today),
+        "yesterday" => return Ok(today - Days::new(1)),
+        "tomorrow" => return Ok(today + Days::new(1)),
+        _ => {}
+    }
+    // An empty input is never a valid date, which gives the error to return if no format is tried
+    let mut result = NaiveDate::parse_from_str("", "%Y-%m-%d");
+    for format in formats {
+        result = NaiveDate::parse_from_str(date_str, format);
+        if result.is_ok() {
+            break;
+        }
+        result = NaiveDate::parse_from_str(
+            &format!("{}-{}", today.format("%Y"), date_str),
+            &format!("%Y-{}", format),
+        );
+        if let Ok(date) = result {
+            if yearless_in_past && date > today {
+                // Parsed again rather than shifted, so that Feb 29 is an error in a common year
+                return NaiveDate::parse_from_str(
+                    &format!("{}-{}", today.year() - 1, date_str),
+                    &format!("%Y-{}", format),
+                );
+            }
+            break;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{Datelike, Locale, NaiveDate, NaiveDateTime, TimeDelta};
+
+    use super::{
+        format_duration, format_time_ago, get_today_date, parse_date_from_str,
+        parse_date_relative_to, weekday_name,
+    };
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(TimeDelta::minutes(0)), "0m");
+        assert_eq!(format_duration(TimeDelta::minutes(59)), "59m");
+        assert_eq!(format_duration(TimeDelta::minutes(60)), "1h 0m");
+        assert_eq!(format_duration(TimeDelta::minutes(133)), "2h 13m");
+        assert_eq!(format_duration(TimeDelta::minutes(-5)), "0m");
+    }
+
+    #[test]
+    fn test_format_time_ago() {
+        let now = NaiveDateTime::parse_from_str("2024-05-12 14:30", "%Y-%m-%d %H:%M").unwrap();
+        let time_ago = |duration: TimeDelta| format_time_ago(now - duration, now);
+        assert_eq!(time_ago(TimeDelta::seconds(59)), "just now");
+        assert_eq!(time_ago(TimeDelta::minutes(-5)), "just now");
+        assert_eq!(time_ago(TimeDelta::minutes(59)), "59m ago");
+        assert_eq!(time_ago(TimeDelta::minutes(150)), "2h ago");
+        assert_eq!(time_ago(TimeDelta::hours(24)), "1d ago");
+        assert_eq!(time_ago(TimeDelta::days(364)), "364d ago");
+        assert_eq!(time_ago(TimeDelta::days(800)), "2y ago");
+    }
+
+    #[test]
+    fn test_weekday_name() {
+        let date = NaiveDate::from_ymd_opt(2024, 5, 13).unwrap();
+        assert_eq!(weekday_name(date, Locale::en_US), "Mon");
+        assert_eq!(weekday_name(date, Locale::de_DE), "Mo");
+        assert_eq!(weekday_name(date, Locale::fr_FR), "lun.");
+    }
+
+    #[test]
+    fn test_parse_date_from_str() {
+        let today = get_today_date();
+        let formats = ["%Y-%m-%d", "%m-%d"].map(String::from);
+        assert_eq!(
+            parse_date_from_str("2024-5-12", &formats, false),
+            Ok(NaiveDate::from_ymd_opt(2024, 5, 12).unwrap())
+        );
+        assert_eq!(
+            parse_date_from_str("12-02", &formats, false),
+            Ok(today.with_day(2).unwrap().with_month(12).unwrap())
+        );
+        assert!(parse_date_from_str("12/02/2024", &formats, false).is_err());
+        assert_eq!(parse_date_from_str("today", &formats, false), Ok(today));
+        assert_eq!(
+            parse_date_from_str("yesterday", &formats, false),
+            Ok(today.pred_opt().unwrap())
+        );
+        assert_eq!(
+            parse_date_from_str("tomorrow", &formats, false),
+            Ok(today.succ_opt().unwrap())
+        );
+
+        let formats = ["%d/%m/%Y", "%m/%d"].map(String::from);
+        assert_eq!(
+            parse_date_from_str("12/02/2024", &formats, false),
+            Ok(NaiveDate::from_ymd_opt(2024, 2, 12).unwrap())
+        );
+        assert_eq!(
+            parse_date_from_str("12/02", &formats, false),
+            Ok(today.with_day(2).unwrap().with_month(12).unwrap())
+        );
+        assert!(parse_date_from_str("2024-5-12", &formats, false).is_err());
+        assert!(parse_date_from_str("2024-5-12", &[], false).is_err());
+    }
+
+    #[test]
+    fn test_parse_yearless_date_in_past() {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 15).unwrap();
+        let formats = ["%Y-%m-%d", "%m-%d"].map(String::from);
+        let parse = |date_str, yearless_in_past| {
+            parse_date_relative_to(date_str, &formats, yearless_in_past, today).unwrap()
+        };
+        assert_eq!(
+            parse("03-01", true),
+            NaiveDate::from_ymd_opt(2024, 3, 1).unwrap()
+        );
+        assert_eq!(parse("06-15", true), today);
+        assert_eq!(
+            parse("12-25", true),
+            NaiveDate::from_ymd_opt(2023, 12, 25).unwrap()
+        );
+        assert_eq!(
+            parse("12-25", false),
+            NaiveDate::from_ymd_opt(2024, 12, 25).unwrap()
+        );
+        // A full date is never moved
+        assert_eq!(
+            parse("2024-12-25", true),
+            NaiveDate::from_ymd_opt(2024, 12, 25).unwrap()
+        );
+        assert_eq!(
+            parse("tomorrow", true),
+            NaiveDate::from_ymd_opt(2024, 6, 16).unwrap()
+        );
+
+        // Feb 29 of the previous year doesn't exist
+        let today = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+        assert!(parse_date_relative_to("02-29", &formats, true, today).is_err());
+        let today = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        assert_eq!(
+            parse_date_relative_to("02-29", &formats, true, today).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+        );
+    }
+}
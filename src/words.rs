@@ -0,0 +1,163 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+
+use crate::{config::entries, error::Error, log_item::LogItem};
+
+/// The common English words left out of the word frequencies unless `--no-stopwords` is given.
+const STOPWORDS: &[&str] = &[
+    "a", "about", "after", "all", "am", "an", "and", "any", "are", "as", "at", "be", "been", "but",
+    "by", "can", "could", "did", "do", "does", "for", "from", "had", "has", "have", "he", "her",
+    "him", "his", "i", "i'm", "if", "in", "into", "is", "it", "it's", "its", "just", "me", "more",
+    "my", "no", "not", "of", "on", "or", "our", "out", "she", "so", "some", "than", "that", "the",
+    "their", "them", "then", "there", "they", "this", "to", "up", "us", "was", "we", "were",
+    "what", "when", "which", "who", "will", "with", "would", "you", "your",
+];
+
+/// Splits `content` into lowercase words at white space, without the punctuation around
+/// them. The punctuation inside a word, e.g. of `don't` or `e-mail`, is kept.
+fn words(content: &str) -> impl Iterator<Item = String> + '_ {
+    content
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric()))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Adds the words in the content of `log_item` to `counts`, without the stopwords
+/// if `skip_stopwords` is true.
+fn add_words(counts: &mut HashMap<String, usize>, log_item: &LogItem, skip_stopwords: bool) {
+    for word in words(log_item.content()) {
+        if skip_stopwords && STOPWORDS.contains(&word.as_str()) {
+            continue;
+        }
+        *counts.entry(word).or_default() += 1;
+    }
+}
+
+/// Sorts the words by descending count, and alphabetically if the counts are equal.
+fn sort_counts(counts: HashMap<String, usize>) -> Vec<(String, usize)> {
+    let mut ret: Vec<(String, usize)> = counts.into_iter().collect();
+    ret.sort_by(|(word1, count1), (word2, count2)| count2.cmp(count1).then(word1.cmp(word2)));
+    ret
+}
+
+/// Counts the words in the content of `log_items`, without the stopwords if `skip_stopwords`
+/// is true. The words are sorted by descending count, and alphabetically if the counts are equal.
+pub fn count_words(
+    log_items: impl IntoIterator<Item = impl Borrow<LogItem>>,
+    skip_stopwords: bool,
+) -> Vec<(String, usize)> {
+    let mut counts = HashMap::new();
+    for log_item in log_items {
+        add_words(&mut counts, log_item.borrow(), skip_stopwords);
+    }
+    sort_counts(counts)
+}
+
+/// Writes the `top` most frequent words in all log messages in `log_dir_path` to `output`,
+/// one per line after its count. The log files are read one at a time.
+pub fn write_word_frequencies(
+    output: &mut impl Write,
+    log_dir_path: &Path,
+    top: usize,
+    skip_stopwords: bool,
+) -> Result<(), Error> {
+    if !log_dir_path.exists() {
+        return Err(Error::LogDirNotFound(log_dir_path.to_path_buf()));
+    }
+
+    // The counting stops at the first log file that can't be read
+    let mut read_error = None;
+    let log_items = entries(log_dir_path)
+        .map_while(|log_item| log_item.map_err(|error| read_error = Some(error)).ok());
+    let mut rows = count_words(log_items, skip_stopwords);
+    if let Some(error) = read_error {
+        return Err(Error::Io(error));
+    }
+    rows.truncate(top);
+
+    let count_width = rows
+        .iter()
+        .map(|(_, count)| count.to_string().len())
+        .max()
+        .unwrap_or_default();
+    for (word, count) in rows {
+        writeln!(output, "{:>count_width$}  {}", count, word).map_err(Error::Io)?;
+    }
+    Ok(())
+}
+
+/// Prints the `top` most frequent words in all log messages with their counts.
+pub fn print_word_frequencies(
+    log_dir_path: &Path,
+    top: usize,
+    skip_stopwords: bool,
+) -> Result<(), Error> {
+    write_word_frequencies(
+        &mut std::io::stdout().lock(),
+        log_dir_path,
+        top,
+        skip_stopwords,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDateTime;
+
+    use crate::log_item::LogItem;
+
+    use super::{count_words, write_word_frequencies};
+
+    #[test]
+    fn test_count_words() {
+        let date_time = NaiveDateTime::default();
+        let log_items = [
+            LogItem::new(date_time, "The cat sat on the mat."),
+            LogItem::new(date_time, "A CAT, a hat; the cat's #hat!"),
+            LogItem::new(date_time, "-- don't -- 42"),
+        ];
+        assert_eq!(
+            count_words(&log_items, true),
+            [
+                ("cat".to_owned(), 2),
+                ("hat".to_owned(), 2),
+                ("42".to_owned(), 1),
+                ("cat's".to_owned(), 1),
+                ("don't".to_owned(), 1),
+                ("mat".to_owned(), 1),
+                ("sat".to_owned(), 1),
+            ]
+        );
+        let counts = count_words(&log_items, false);
+        assert_eq!(counts[0], ("the".to_owned(), 3));
+        assert!(counts.contains(&("a".to_owned(), 2)));
+    }
+
+    #[test]
+    fn test_write_word_frequencies() {
+        let log_dir = tempfile::tempdir().unwrap();
+        let content: String = (0..12)
+            .map(|i| format!("[2024-05-01 08:{:02}] cat\n", i))
+            .chain(["[2024-05-01 09:00] dog dog the bird\n".to_owned()])
+            .collect();
+        std::fs::write(log_dir.path().join("2024-05-01.log"), content).unwrap();
+
+        let mut output = Vec::new();
+        write_word_frequencies(&mut output, log_dir.path(), 2, true).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "12  cat\n 2  dog\n");
+
+        let mut output = Vec::new();
+        write_word_frequencies(&mut output, log_dir.path(), 10, false).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "12  cat\n 2  dog\n 1  bird\n 1  the\n"
+        );
+
+        let mut output = Vec::new();
+        write_word_frequencies(&mut output, log_dir.path(), 0, true).unwrap();
+        assert!(output.is_empty());
+    }
+}